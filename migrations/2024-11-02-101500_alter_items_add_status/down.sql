@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX items_status_idx;
+
+ALTER TABLE items
+DROP COLUMN status,
+DROP COLUMN last_event_ts;
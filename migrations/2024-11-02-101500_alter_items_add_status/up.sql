@@ -0,0 +1,25 @@
+-- Your SQL goes here
+ALTER TABLE items
+ADD COLUMN status VARCHAR NOT NULL DEFAULT 'registered', -- status derived from the last event
+ADD COLUMN last_event_ts TIMESTAMP; -- time of the last event
+
+UPDATE items
+SET status = CASE last_events.data->>'kind'
+        WHEN 'Manufactured' THEN 'in_stock'
+        WHEN 'PutIntoService' THEN 'in_service'
+        WHEN 'Inspected' THEN 'in_service'
+        WHEN 'Borrowed' THEN 'borrowed'
+        WHEN 'Returned' THEN 'in_service'
+        WHEN 'Retired' THEN 'retired'
+        WHEN 'Lost' THEN 'lost'
+        WHEN 'Found' THEN 'found'
+    END,
+    last_event_ts = last_events.ts
+FROM (
+    SELECT DISTINCT ON (item_id) item_id, ts, data
+    FROM events
+    ORDER BY item_id, ts DESC
+) AS last_events
+WHERE last_events.item_id = items.id;
+
+CREATE INDEX items_status_idx ON items(status);
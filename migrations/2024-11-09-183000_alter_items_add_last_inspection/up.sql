@@ -0,0 +1,13 @@
+-- Your SQL goes here
+ALTER TABLE items
+ADD COLUMN last_inspection_ts TIMESTAMP; -- time of the last inspection, or of the put into service
+
+UPDATE items
+SET last_inspection_ts = last_inspections.ts
+FROM (
+    SELECT item_id, MAX(ts) AS ts
+    FROM events
+    WHERE data->>'kind' IN ('Inspected', 'PutIntoService')
+    GROUP BY item_id
+) AS last_inspections
+WHERE last_inspections.item_id = items.id;
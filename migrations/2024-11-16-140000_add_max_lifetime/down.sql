@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE tags
+DROP COLUMN max_lifetime_years,
+DROP COLUMN max_service_years;
+
+ALTER TABLE items
+DROP COLUMN max_lifetime_years,
+DROP COLUMN max_service_years,
+DROP COLUMN manufactured_ts,
+DROP COLUMN in_service_ts;
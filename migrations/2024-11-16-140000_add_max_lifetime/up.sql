@@ -0,0 +1,30 @@
+-- Your SQL goes here
+ALTER TABLE items
+ADD COLUMN max_lifetime_years INTEGER, -- maximum lifetime from manufacture
+ADD COLUMN max_service_years INTEGER, -- maximum lifetime from put into service
+ADD COLUMN manufactured_ts TIMESTAMP, -- time of manufacture
+ADD COLUMN in_service_ts TIMESTAMP; -- time of put into service
+
+ALTER TABLE tags
+ADD COLUMN max_lifetime_years INTEGER, -- default maximum lifetime from manufacture for tagged items
+ADD COLUMN max_service_years INTEGER; -- default maximum lifetime from put into service for tagged items
+
+UPDATE items
+SET manufactured_ts = manufactured.ts
+FROM (
+    SELECT item_id, MIN(ts) AS ts
+    FROM events
+    WHERE data->>'kind' = 'Manufactured'
+    GROUP BY item_id
+) AS manufactured
+WHERE manufactured.item_id = items.id;
+
+UPDATE items
+SET in_service_ts = in_service.ts
+FROM (
+    SELECT item_id, MIN(ts) AS ts
+    FROM events
+    WHERE data->>'kind' = 'PutIntoService'
+    GROUP BY item_id
+) AS in_service
+WHERE in_service.item_id = items.id;
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE tags
+DROP COLUMN inspection_after_falls,
+DROP COLUMN inspection_after_fall_factor;
+
+ALTER TABLE items
+DROP COLUMN inspection_after_falls,
+DROP COLUMN inspection_after_fall_factor,
+DROP COLUMN falls_since_inspection,
+DROP COLUMN worst_fall_factor_since_inspection,
+DROP COLUMN last_used_ts;
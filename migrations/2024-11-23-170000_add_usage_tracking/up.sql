@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE items
+ADD COLUMN inspection_after_falls INTEGER, -- number of falls after which an inspection is needed
+ADD COLUMN inspection_after_fall_factor REAL, -- fall factor after which an inspection is needed
+ADD COLUMN falls_since_inspection INTEGER NOT NULL DEFAULT 0, -- falls held since the last inspection
+ADD COLUMN worst_fall_factor_since_inspection REAL, -- worst fall factor held since the last inspection
+ADD COLUMN last_used_ts TIMESTAMP; -- time of the last usage report
+
+ALTER TABLE tags
+ADD COLUMN inspection_after_falls INTEGER, -- default falls threshold for tagged items
+ADD COLUMN inspection_after_fall_factor REAL; -- default fall factor threshold for tagged items
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE users
+-- If the user is authorized to log maintenance on an item
+ADD COLUMN perm_action_maintain BOOLEAN NOT NULL DEFAULT false;
+
+-- inspectors used to log maintenance in their inspection comments
+UPDATE users SET perm_action_maintain = perm_action_inspect;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+UPDATE items
+SET status = 'in_service'
+WHERE status = 'quarantined';
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- items whose last status change is a worrying inspection are now quarantined
+UPDATE items
+SET status = 'quarantined'
+FROM (
+    SELECT DISTINCT ON (item_id) item_id, data
+    FROM events
+    WHERE data->>'kind' NOT IN ('Used', 'Maintained')
+    ORDER BY item_id, ts DESC
+) AS last_events
+WHERE last_events.item_id = items.id
+    AND last_events.data->>'kind' = 'Inspected'
+    AND last_events.data->>'result' IN ('Warning', 'Danger');
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE items
+DROP COLUMN borrower,
+DROP COLUMN borrowed_ts,
+DROP COLUMN due_back;
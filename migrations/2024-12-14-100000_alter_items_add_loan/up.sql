@@ -0,0 +1,16 @@
+-- Your SQL goes here
+ALTER TABLE items
+ADD COLUMN borrower TEXT, -- person who currently borrows the item
+ADD COLUMN borrowed_ts TIMESTAMP, -- time of the current borrow
+ADD COLUMN due_back TIMESTAMP; -- time the item is expected back from the current borrow
+
+UPDATE items
+SET borrower = last_borrows.data->>'borrower',
+    borrowed_ts = last_borrows.ts
+FROM (
+    SELECT DISTINCT ON (item_id) item_id, ts, data
+    FROM events
+    WHERE data->>'kind' = 'Borrowed'
+    ORDER BY item_id, ts DESC
+) AS last_borrows
+WHERE last_borrows.item_id = items.id AND items.status = 'borrowed';
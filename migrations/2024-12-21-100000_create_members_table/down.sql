@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE items
+DROP COLUMN borrower_id;
+
+UPDATE events SET data = data - 'borrower_id' WHERE data->>'kind' = 'Borrowed';
+UPDATE events SET data = data #- '{data,borrower_id}' WHERE data->>'kind' = 'Amended';
+
+DROP TABLE members;
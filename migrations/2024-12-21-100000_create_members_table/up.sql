@@ -0,0 +1,34 @@
+-- Your SQL goes here
+CREATE TABLE members (
+    id BIGSERIAL PRIMARY KEY NOT NULL,
+    name VARCHAR NOT NULL, -- display name of the member
+    email VARCHAR, -- optional contact email
+    phone VARCHAR -- optional contact phone number
+);
+
+-- one member per borrower name found in the history
+INSERT INTO members (name)
+SELECT data->>'borrower' FROM events WHERE data->>'kind' = 'Borrowed'
+UNION
+SELECT data->'data'->>'borrower' FROM events
+WHERE data->>'kind' = 'Amended' AND data->'data'->>'kind' = 'Borrowed';
+
+UPDATE events
+SET data = jsonb_set(data, '{borrower_id}', to_jsonb(members.id))
+FROM members
+WHERE data->>'kind' = 'Borrowed' AND data->>'borrower' = members.name;
+
+UPDATE events
+SET data = jsonb_set(data, '{data,borrower_id}', to_jsonb(members.id))
+FROM members
+WHERE data->>'kind' = 'Amended'
+    AND data->'data'->>'kind' = 'Borrowed'
+    AND data->'data'->>'borrower' = members.name;
+
+ALTER TABLE items
+ADD COLUMN borrower_id BIGINT REFERENCES members(id); -- member who currently borrows the item
+
+UPDATE items
+SET borrower_id = members.id
+FROM members
+WHERE items.borrower = members.name;
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE kits (
+    id BIGSERIAL PRIMARY KEY NOT NULL,
+    name VARCHAR NOT NULL, -- name of the kit
+    closed_ts TIMESTAMP -- time the kit was taken apart, unset while the kit is open
+);
+
+CREATE TABLE kits_items (
+    id BIGSERIAL PRIMARY KEY NOT NULL,
+    kit_id BIGINT NOT NULL, -- kit containing the item
+    item_id BIGINT NOT NULL, -- item belonging to the kit
+    FOREIGN KEY(kit_id) REFERENCES kits(id) ON DELETE CASCADE,
+    FOREIGN KEY(item_id) REFERENCES items(id) ON DELETE CASCADE,
+    UNIQUE(kit_id, item_id)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE kit_templates_tags;
+DROP TABLE kit_templates;
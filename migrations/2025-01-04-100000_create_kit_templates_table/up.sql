@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE kit_templates (
+    id BIGSERIAL PRIMARY KEY NOT NULL,
+    name VARCHAR NOT NULL -- name of the template
+);
+
+CREATE TABLE kit_templates_tags (
+    id BIGSERIAL PRIMARY KEY NOT NULL,
+    kit_template_id BIGINT NOT NULL, -- template requiring the tag
+    tag_id BIGINT NOT NULL, -- tag of the required items
+    quantity INTEGER NOT NULL CHECK (quantity > 0), -- number of items required with this tag
+    FOREIGN KEY(kit_template_id) REFERENCES kit_templates(id) ON DELETE CASCADE,
+    FOREIGN KEY(tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
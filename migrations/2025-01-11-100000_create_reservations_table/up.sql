@@ -0,0 +1,20 @@
+-- Your SQL goes here
+CREATE TABLE reservations (
+    id BIGSERIAL PRIMARY KEY NOT NULL,
+    member_id BIGINT NOT NULL, -- member holding the reservation
+    kit_id BIGINT, -- kit reserved as a whole, if any
+    starts_ts TIMESTAMP NOT NULL, -- start of the reserved period
+    ends_ts TIMESTAMP NOT NULL, -- end of the reserved period, excluded
+    comment TEXT, -- optional comment, ex: name of the trip
+    FOREIGN KEY(member_id) REFERENCES members(id) ON DELETE CASCADE,
+    FOREIGN KEY(kit_id) REFERENCES kits(id) ON DELETE CASCADE,
+    CHECK (starts_ts < ends_ts)
+);
+
+CREATE TABLE reservations_items (
+    id BIGSERIAL PRIMARY KEY NOT NULL,
+    reservation_id BIGINT NOT NULL, -- reservation covering the item
+    item_id BIGINT NOT NULL, -- reserved item
+    FOREIGN KEY(reservation_id) REFERENCES reservations(id) ON DELETE CASCADE,
+    FOREIGN KEY(item_id) REFERENCES items(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE items
+ADD COLUMN archived_ts TIMESTAMP; -- time the item was archived, unset for active items
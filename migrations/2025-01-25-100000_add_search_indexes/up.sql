@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- expressions must be repeated exactly by the search queries for the indexes to be used
+CREATE INDEX items_search_idx ON items
+USING GIN (to_tsvector('simple', name || ' ' || COALESCE(serial_number, '')));
+
+CREATE INDEX events_search_idx ON events
+USING GIN (to_tsvector('simple', COALESCE(data->>'comment', data->'data'->>'comment', '')));
@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use crate::{
+    models::event::{Event, EventData},
+    schema::events,
+};
+
+use super::{
+    item_details::ItemEvent, item_historical_event::fill_borrower, ApiError, ApiResult,
+    Application, AuthenticatedUser, ManageItems,
+};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct AmendEvent {
+    /// Corrected details of the event
+    data: EventData,
+    /// Why the event is corrected
+    reason: String,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(event_id): Path<i64>,
+    Json(AmendEvent { mut data, reason }): Json<AmendEvent>,
+) -> ApiResult<Json<ItemEvent>> {
+    let mut conn = state.database.get().await?;
+    let target = events::table
+        .find(event_id)
+        .get_result::<Event>(&mut conn)
+        .await?;
+    // an amendment fixes the details of an event, not what happened
+    if data.kind() != target.data.kind() {
+        return Err(ApiError::AmendedKindMismatch(
+            target.data.kind(),
+            data.kind(),
+        ));
+    }
+    // the amendment records who corrected the event, not who took part in it
+    let data = if data.keep_people(&target.data) {
+        fill_borrower(&mut conn, data).await?
+    } else {
+        data
+    };
+    let event = Event::insert_historical_event(
+        &mut conn,
+        target.item_id,
+        Utc::now(),
+        EventData::Amended {
+            target_event_id: target.id,
+            data: Box::new(data),
+            reason,
+            validator: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(event.into()))
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use crate::{
+    models::event::{Event, EventData},
+    schema::events,
+};
+
+use super::{item_details::ItemEvent, ApiResult, Application, AuthenticatedUser, ManageItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct VoidEvent {
+    /// Why the event is cancelled
+    reason: String,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(event_id): Path<i64>,
+    Json(VoidEvent { reason }): Json<VoidEvent>,
+) -> ApiResult<Json<ItemEvent>> {
+    let mut conn = state.database.get().await?;
+    let target = events::table
+        .find(event_id)
+        .get_result::<Event>(&mut conn)
+        .await?;
+    let event = Event::insert_historical_event(
+        &mut conn,
+        target.item_id,
+        Utc::now(),
+        EventData::Voided {
+            target_event_id: target.id,
+            reason,
+            validator: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(event.into()))
+}
@@ -0,0 +1,113 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{
+    BelongingToDsl as _, ExpressionMethods as _, GroupedBy as _, QueryDsl as _,
+    SelectableHelper as _,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiError, ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{
+    models::{
+        item::{Item as ItemModel, ItemStatus},
+        tag::{ItemTag, Tag as TagModel},
+    },
+    schema::{items, tags},
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct InspectionsDueQuery {
+    /// How far ahead to look for upcoming inspections, in days (`30d`) or weeks (`4w`).
+    /// Defaults to 30 days.
+    within: Option<String>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct InspectionDue {
+    /// Id of the item
+    id: i64,
+    /// Name of the item
+    name: String,
+    /// Optional serial number
+    serial_number: Option<String>,
+    /// Current status of the item
+    status: ItemStatus,
+    /// Date when the next inspection is due
+    next_inspection_due: chrono::DateTime<Utc>,
+    /// Days left before the inspection is due, negative if overdue
+    days_left: i64,
+}
+
+fn parse_duration(value: &str) -> Option<TimeDelta> {
+    let (number, unit) = match value.char_indices().last()? {
+        (i, c) if c.is_ascii_alphabetic() => (&value[..i], Some(c)),
+        _ => (value, None),
+    };
+    let number: i64 = number.parse().ok()?;
+    // the horizon lies in the future, overdue inspections are always listed
+    if number < 0 {
+        return None;
+    }
+    match unit {
+        None | Some('d') => TimeDelta::try_days(number),
+        Some('w') => TimeDelta::try_weeks(number),
+        _ => None,
+    }
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Query(InspectionsDueQuery { within }): Query<InspectionsDueQuery>,
+) -> ApiResult<Json<Vec<InspectionDue>>> {
+    let within = match within {
+        Some(within) => parse_duration(&within).ok_or(ApiError::InvalidDuration(within))?,
+        None => TimeDelta::days(30),
+    };
+    let now = Utc::now();
+    let horizon = now + within;
+
+    let mut conn = state.database.get().await?;
+    let items = items::table
+        .filter(items::status.eq_any([
+            ItemStatus::InService,
+            ItemStatus::Borrowed,
+            ItemStatus::Found,
+            ItemStatus::Quarantined,
+        ]))
+        .filter(items::archived_ts.is_null())
+        .get_results::<ItemModel>(&mut conn)
+        .await?;
+    let tags = ItemTag::belonging_to(&items)
+        .inner_join(tags::table)
+        .select((ItemTag::as_select(), TagModel::as_select()))
+        .get_results::<(ItemTag, TagModel)>(&mut conn)
+        .await?
+        .grouped_by(&items);
+
+    let mut due = items
+        .into_iter()
+        .zip(tags)
+        .filter_map(|(item, item_tags)| {
+            let next_inspection_due =
+                item.next_inspection_due(item_tags.iter().map(|(_, tag)| tag))?;
+            (next_inspection_due <= horizon).then(|| InspectionDue {
+                id: item.id,
+                name: item.name,
+                serial_number: item.serial_number,
+                status: item.status,
+                next_inspection_due,
+                days_left: (next_inspection_due - now).num_days(),
+            })
+        })
+        .collect::<Vec<_>>();
+    // most urgent first
+    due.sort_by_key(|inspection| inspection.next_inspection_due);
+
+    Ok(Json(due))
+}
@@ -0,0 +1,142 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{BelongingToDsl as _, QueryDsl as _, SelectableHelper as _};
+use diesel_async::RunQueryDsl as _;
+
+use super::{
+    Administrate, ApiClaims, ApiResult, Application, AuthenticatedUser, ClaimPermission,
+    InspectItems, LendItems, MaintainItems, ManageItems, NoPermission,
+};
+use crate::{
+    models::{
+        event::EventKind,
+        item::{Item as ItemModel, ItemStatus},
+        lifecycle::Lifecycle,
+        reservation::Reservation,
+        tag::{ItemTag, Tag as TagModel},
+    },
+    schema::*,
+};
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(tag = "reason")]
+pub enum ActionDenied {
+    /// The lifecycle does not allow this event for the current status of the item
+    InvalidTransition { status: ItemStatus },
+    /// The user does not have the permission for this action
+    MissingPermission,
+    /// The item is past its end of life
+    EndOfLife { end_of_life: chrono::DateTime<Utc> },
+    /// The item is reserved, only the holder of the reservation can borrow it
+    ItemReserved { reservation_id: i64, member_id: i64 },
+    /// The item is archived, it must be restored first
+    ItemArchived,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct ItemAction {
+    /// Kind of event the action would log
+    kind: EventKind,
+    /// Whether the current user can take this action right now
+    allowed: bool,
+    /// Why the action is not allowed, empty if it is
+    reasons: Vec<ActionDenied>,
+    /// Time a borrow must be due back by, as another reservation starts then
+    due_before: Option<chrono::DateTime<Utc>>,
+}
+
+/// Whether the claims allow logging an event of this kind, matching the endpoint permissions
+fn has_permission(kind: EventKind, claims: &ApiClaims) -> bool {
+    match kind {
+        EventKind::Manufactured | EventKind::PutIntoService | EventKind::Retired => {
+            ManageItems::check(claims)
+        }
+        EventKind::Inspected | EventKind::Quarantined => InspectItems::check(claims),
+        EventKind::Borrowed
+        | EventKind::Returned
+        | EventKind::Lost
+        | EventKind::Found
+        | EventKind::Used => LendItems::check(claims),
+        EventKind::Maintained => MaintainItems::check(claims),
+        EventKind::Voided | EventKind::Amended | EventKind::Edited => ManageItems::check(claims),
+        EventKind::Recorded => Administrate::check(claims),
+    }
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+) -> ApiResult<Json<Vec<ItemAction>>> {
+    let mut conn = state.database.get().await?;
+    let item = items::table
+        .find(item_id)
+        .get_result::<ItemModel>(&mut conn)
+        .await?;
+    let tags = ItemTag::belonging_to(&item)
+        .inner_join(tags::table)
+        .select(TagModel::as_select())
+        .get_results::<TagModel>(&mut conn)
+        .await?;
+    let now = Utc::now();
+    let end_of_life = item
+        .end_of_life(&tags)
+        .filter(|end_of_life| *end_of_life <= now);
+    // borrows are refused during a reservation, and must end before the next one
+    let (reservation, next_reservation_ts) =
+        match Reservation::next_at(&mut conn, item.id, now.naive_utc()).await? {
+            Some((reservation_id, member_id, starts_ts)) if starts_ts <= now.naive_utc() => {
+                (Some((reservation_id, member_id)), None)
+            }
+            Some((_, _, starts_ts)) => (
+                None,
+                Some(chrono::DateTime::from_naive_utc_and_offset(starts_ts, Utc)),
+            ),
+            None => (None, None),
+        };
+    let lifecycle = Lifecycle::current();
+
+    Ok(Json(
+        EventKind::ALL
+            .into_iter()
+            // corrections and audits are not actions on the item
+            .filter(|kind| kind.is_lifecycle())
+            .map(|kind| {
+                let mut reasons = vec![];
+                if item.archived_ts.is_some() {
+                    reasons.push(ActionDenied::ItemArchived);
+                }
+                if !lifecycle.allows(item.status, kind) {
+                    reasons.push(ActionDenied::InvalidTransition {
+                        status: item.status,
+                    });
+                }
+                if !has_permission(kind, &auth.claims) {
+                    reasons.push(ActionDenied::MissingPermission);
+                }
+                if let (EventKind::Borrowed, Some(end_of_life)) = (kind, end_of_life) {
+                    reasons.push(ActionDenied::EndOfLife { end_of_life });
+                }
+                if let (EventKind::Borrowed, Some((reservation_id, member_id))) =
+                    (kind, reservation)
+                {
+                    reasons.push(ActionDenied::ItemReserved {
+                        reservation_id,
+                        member_id,
+                    });
+                }
+                ItemAction {
+                    kind,
+                    allowed: reasons.is_empty(),
+                    reasons,
+                    due_before: next_reservation_ts.filter(|_| kind == EventKind::Borrowed),
+                }
+            })
+            .collect(),
+    ))
+}
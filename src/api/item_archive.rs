@@ -0,0 +1,72 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{ExpressionMethods as _, OptionalExtension as _, QueryDsl as _};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use super::{ApiError, ApiResult, Application, AuthenticatedUser, ManageItems};
+use crate::{
+    models::item::{Item as ItemModel, ItemStatus},
+    schema::*,
+};
+
+/// Hide an item from the default lists, its history is kept.
+///
+/// Items still lent, in an open kit or reserved must be freed first,
+/// as archived items cannot be returned, reserved or added to kits.
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    conn.transaction(|conn| {
+        async move {
+            let item = items::table
+                .find(item_id)
+                .for_update()
+                .get_result::<ItemModel>(conn)
+                .await?;
+            // already archived items keep their archive date
+            if item.archived_ts.is_some() {
+                return Ok(());
+            }
+            if item.status == ItemStatus::Borrowed {
+                return Err(ApiError::ItemBorrowed(item_id));
+            }
+            let open_kit_id = kits_items::table
+                .inner_join(kits::table)
+                .filter(kits::closed_ts.is_null())
+                .filter(kits_items::item_id.eq(item_id))
+                .select(kits_items::kit_id)
+                .first::<i64>(conn)
+                .await
+                .optional()?;
+            if let Some(kit_id) = open_kit_id {
+                return Err(ApiError::ItemInOpenKit(item_id, kit_id));
+            }
+            let reservation_id = reservations_items::table
+                .inner_join(reservations::table)
+                .filter(reservations_items::item_id.eq(item_id))
+                .filter(reservations::ends_ts.gt(Utc::now().naive_utc()))
+                .select(reservations::id)
+                .first::<i64>(conn)
+                .await
+                .optional()?;
+            if let Some(reservation_id) = reservation_id {
+                return Err(ApiError::ReservationConflict(item_id, reservation_id));
+            }
+
+            diesel::update(items::table.find(item_id))
+                .set(items::archived_ts.eq(Utc::now().naive_utc()))
+                .execute(conn)
+                .await?;
+            Ok::<_, ApiError>(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    Ok(Json(()))
+}
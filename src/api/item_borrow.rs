@@ -0,0 +1,192 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use crate::{
+    models::{
+        event::{Event, EventData},
+        member::Member,
+    },
+    schema::members,
+};
+
+use super::{ApiResult, Application, AuthenticatedUser, LendItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct BorrowItem {
+    /// Member who borrows the item
+    borrower_id: i64,
+    /// Time the item is expected back in UTC
+    due_back: Option<chrono::DateTime<Utc>>,
+    /// Time of the borrow in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(BorrowItem {
+        borrower_id,
+        due_back,
+        ts,
+    }): Json<BorrowItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    let borrower = members::table
+        .find(borrower_id)
+        .get_result::<Member>(&mut conn)
+        .await?;
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Borrowed {
+            borrower_id: borrower.id,
+            borrower: borrower.name,
+            validator: auth.claims.login,
+            due_back,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use axum::{
+        extract::{Path, State},
+        Json,
+    };
+    use chrono::{TimeDelta, Utc};
+    use diesel::{Connection as _, QueryDsl as _};
+    use diesel_async::RunQueryDsl as _;
+    use diesel_migrations::MigrationHarness as _;
+    use jwt_simple::algorithms::HS256Key;
+
+    use super::{handler, BorrowItem};
+    use crate::{
+        api::{ApiClaims, Application, AuthenticatedUser, LendItems},
+        db::create_pool,
+        models::{
+            event::{Event, EventData},
+            item::{InsertItem, Item},
+            member::{InsertMember, Member},
+        },
+        schema::{items, members},
+        MIGRATIONS,
+    };
+
+    const BORROWS: usize = 8;
+
+    fn lender() -> AuthenticatedUser<LendItems> {
+        AuthenticatedUser {
+            claims: ApiClaims {
+                login: "lender".to_owned(),
+                perm_users: false,
+                perm_tags: false,
+                perm_items: false,
+                perm_action_inspect: false,
+                perm_action_lend: true,
+                perm_action_maintain: false,
+            },
+            phantom: PhantomData,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore = "needs a Postgres database in DATABASE_URL"]
+    async fn concurrent_borrows_only_accept_one() {
+        let url = std::env::var("DATABASE_URL").unwrap();
+        diesel::pg::PgConnection::establish(&url)
+            .unwrap()
+            .run_pending_migrations(MIGRATIONS)
+            .unwrap();
+        let application = Application {
+            database: create_pool(&url),
+            jwt_secret: HS256Key::generate(),
+        };
+        let mut conn = application.database.get().await.unwrap();
+
+        let item = diesel::insert_into(items::table)
+            .values(InsertItem {
+                name: "Concurrently borrowed rope".to_owned(),
+                inspection_period_days: None,
+                serial_number: None,
+                max_lifetime_years: None,
+                max_service_years: None,
+                inspection_after_falls: None,
+                inspection_after_fall_factor: None,
+            })
+            .returning(items::all_columns)
+            .get_result::<Item>(&mut conn)
+            .await
+            .unwrap();
+        let member = diesel::insert_into(members::table)
+            .values(InsertMember {
+                name: "Concurrent borrower".to_owned(),
+                email: None,
+                phone: None,
+            })
+            .returning(members::all_columns)
+            .get_result::<Member>(&mut conn)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        Event::insert_event(
+            &mut conn,
+            item.id,
+            now - TimeDelta::minutes(2),
+            EventData::Manufactured {},
+        )
+        .await
+        .unwrap();
+        Event::insert_event(
+            &mut conn,
+            item.id,
+            now - TimeDelta::minutes(1),
+            EventData::PutIntoService {},
+        )
+        .await
+        .unwrap();
+
+        let borrows = (0..BORROWS)
+            .map(|_| {
+                tokio::spawn(handler(
+                    lender(),
+                    State(application.clone()),
+                    Path(item.id),
+                    Json(BorrowItem {
+                        borrower_id: member.id,
+                        due_back: None,
+                        ts: None,
+                    }),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let mut accepted = 0;
+        for borrow in borrows {
+            if borrow.await.unwrap().is_ok() {
+                accepted += 1;
+            }
+        }
+
+        diesel::delete(items::table.find(item.id))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        diesel::delete(members::table.find(member.id))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(accepted, 1);
+    }
+}
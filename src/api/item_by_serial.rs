@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::{
+    BelongingToDsl as _, ExpressionMethods as _, GroupedBy as _, QueryDsl as _,
+    SelectableHelper as _,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{item_list::Item, ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{
+    models::{
+        item::Item as ItemModel,
+        tag::{ItemTag, Tag as TagModel},
+    },
+    schema::{items, tags},
+};
+
+/// Serial numbers are only unique per product, so several items can match
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Path(serial_number): Path<String>,
+) -> ApiResult<Json<Vec<Item>>> {
+    let mut conn = state.database.get().await?;
+    let items = items::table
+        .filter(items::serial_number.eq(serial_number))
+        .order_by((items::name.asc(), items::id.asc()))
+        .get_results::<ItemModel>(&mut conn)
+        .await?;
+    let tags = ItemTag::belonging_to(&items)
+        .inner_join(tags::table)
+        .select((ItemTag::as_select(), TagModel::as_select()))
+        .get_results::<(ItemTag, TagModel)>(&mut conn)
+        .await?
+        .grouped_by(&items);
+
+    Ok(Json(
+        items
+            .into_iter()
+            .zip(tags)
+            .map(|(item_model, item_tags)| (item_model, item_tags).into())
+            .collect::<Vec<Item>>(),
+    ))
+}
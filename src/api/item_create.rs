@@ -1,13 +1,16 @@
 use axum::{extract::State, Json};
 use chrono::Utc;
-use diesel::{data_types::PgInterval, BelongingToDsl};
+use diesel::{
+    data_types::PgInterval, sql_types::Text, BelongingToDsl, ExpressionMethods as _, QueryDsl as _,
+    SelectableHelper as _,
+};
 use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection as _, RunQueryDsl as _};
 
 use crate::{
     models::{
         event::{Event, EventData},
         item::{InsertItem as InsertItemModel, Item as ItemModel},
-        tag::{InsertItemTag, ItemTag},
+        tag::{InsertItemTag, ItemTag, Tag as TagModel},
     },
     schema::*,
 };
@@ -20,33 +23,99 @@ pub struct CreateItem {
     name: String,
     inspection_period_days: Option<i32>,
     serial_number: Option<String>,
+    max_lifetime_years: Option<i32>,
+    max_service_years: Option<i32>,
+    inspection_after_falls: Option<i32>,
+    inspection_after_fall_factor: Option<f32>,
     tags: Vec<i64>,
     manufactured_on: Option<chrono::DateTime<Utc>>,
     put_into_service_on: Option<chrono::DateTime<Utc>>,
+    /// Refuse the item if another item with the same name has the same serial number
+    reject_duplicate_serial: Option<bool>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct CreatedItem {
+    /// The created item
+    item: Item,
+    /// Ids of other items with the same name and serial number
+    duplicate_serials: Vec<i64>,
+}
+
+/// Ids of the other active items with the same name and serial number.
+///
+/// The same serial number can be used by different products.
+///
+/// Must run in the transaction saving the item, which it serializes with the
+/// other ones saving the same name and serial number.
+pub(super) async fn duplicate_serials(
+    conn: &mut diesel_async::AsyncPgConnection,
+    name: &str,
+    serial_number: Option<&str>,
+    item_id: Option<i64>,
+) -> ApiResult<Vec<i64>> {
+    let Some(serial_number) = serial_number else {
+        return Ok(vec![]);
+    };
+    // nothing can be locked when there is no duplicate yet
+    diesel::sql_query("SELECT pg_advisory_xact_lock(hashtext($1 || '\n' || $2))")
+        .bind::<Text, _>(name)
+        .bind::<Text, _>(serial_number)
+        .execute(conn)
+        .await?;
+    Ok(items::table
+        .filter(items::name.eq(name))
+        .filter(items::serial_number.eq(serial_number))
+        .filter(items::archived_ts.is_null())
+        .select(items::id)
+        .get_results::<i64>(conn)
+        .await?
+        .into_iter()
+        .filter(|id| Some(*id) != item_id)
+        .collect())
 }
 
 pub async fn handler(
     _auth: AuthenticatedUser<ManageItems>,
     state: State<Application>,
     Json(data): Json<CreateItem>,
-) -> ApiResult<Json<Item>> {
+) -> ApiResult<Json<CreatedItem>> {
     let mut conn = state.database.get().await?;
     let CreateItem {
         tags,
         name,
         serial_number,
         inspection_period_days,
+        max_lifetime_years,
+        max_service_years,
+        inspection_after_falls,
+        inspection_after_fall_factor,
         manufactured_on,
         put_into_service_on,
+        reject_duplicate_serial,
     } = data;
-    let (item, item_tags) = conn
+    let (item, item_tags, duplicate_serials) = conn
         .transaction(|mut conn| {
             async move {
+                let duplicate_serials =
+                    duplicate_serials(conn, &name, serial_number.as_deref(), None).await?;
+                if !duplicate_serials.is_empty() && reject_duplicate_serial.unwrap_or(false) {
+                    return Err(ApiError::DuplicateSerial(
+                        name,
+                        serial_number.unwrap_or_default(),
+                    ));
+                }
+
                 let item = diesel::insert_into(items::table)
                     .values(InsertItemModel {
                         name,
                         serial_number,
                         inspection_period_days: inspection_period_days.map(PgInterval::from_days),
+                        max_lifetime_years,
+                        max_service_years,
+                        inspection_after_falls,
+                        inspection_after_fall_factor,
                     })
                     .returning(items::all_columns)
                     .get_result::<ItemModel>(&mut conn)
@@ -79,14 +148,19 @@ pub async fn handler(
                 }
 
                 let item_tags = ItemTag::belonging_to(&item)
-                    .get_results::<ItemTag>(&mut conn)
+                    .inner_join(tags::table)
+                    .select((ItemTag::as_select(), TagModel::as_select()))
+                    .get_results::<(ItemTag, TagModel)>(&mut conn)
                     .await?;
 
-                Ok::<_, ApiError>((item, item_tags))
+                Ok::<_, ApiError>((item, item_tags, duplicate_serials))
             }
             .scope_boxed()
         })
         .await?;
 
-    Ok(Json((item, item_tags).into()))
+    Ok(Json(CreatedItem {
+        item: (item, item_tags).into(),
+        duplicate_serials,
+    }))
 }
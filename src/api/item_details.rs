@@ -1,17 +1,19 @@
+use std::collections::HashMap;
+
 use axum::{
     extract::{Path, State},
     Json,
 };
 use chrono::Utc;
-use diesel::{BelongingToDsl as _, ExpressionMethods as _, QueryDsl as _};
+use diesel::{BelongingToDsl as _, ExpressionMethods as _, QueryDsl as _, SelectableHelper as _};
 use diesel_async::RunQueryDsl as _;
 
 use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
 use crate::{
     models::{
-        event::{Event, EventData},
-        item::Item as ItemModel,
-        tag::ItemTag,
+        event::{Event, EventData, UsageCondition},
+        item::{Item as ItemModel, ItemStatus},
+        tag::{ItemTag, Tag as TagModel},
     },
     schema::*,
 };
@@ -29,10 +31,86 @@ pub struct ItemDetails {
     serial_number: Option<String>,
     /// Ids of all tags associated to this item
     tags: Vec<i64>,
+    /// Current status of the item
+    status: ItemStatus,
+    /// Time of the last event of the item
+    last_event_ts: Option<chrono::DateTime<Utc>>,
+    /// Date when the next inspection is due
+    next_inspection_due: Option<chrono::DateTime<Utc>>,
+    /// Optional maximum lifetime from manufacture, overrides the one of the tags
+    max_lifetime_years: Option<i32>,
+    /// Optional maximum lifetime from put into service, overrides the one of the tags
+    max_service_years: Option<i32>,
+    /// Optional number of falls after which the item must be inspected, overrides the one of the tags
+    inspection_after_falls: Option<i32>,
+    /// Optional fall factor after which the item must be inspected, overrides the one of the tags
+    inspection_after_fall_factor: Option<f32>,
+    /// Date after which the item must not be used anymore
+    end_of_life: Option<chrono::DateTime<Utc>>,
+    /// Whether the item is past its end of life
+    past_end_of_life: bool,
+    /// Time the item was archived, unset for active items
+    archived_ts: Option<chrono::DateTime<Utc>>,
+    /// Usage reported for this item
+    usage: UsageSummary,
     /// Events for this item
     events: Vec<ItemEvent>,
 }
 
+#[derive(serde::Serialize, ts_rs::TS, Default)]
+#[ts(export)]
+pub struct UsageSummary {
+    /// Number of sessions the item was used in
+    sessions: u32,
+    /// Total number of falls held by the item
+    falls: u32,
+    /// Worst fall factor ever held by the item
+    worst_fall_factor: Option<f32>,
+    /// Number of sessions in wet conditions
+    wet_sessions: u32,
+    /// Number of sessions in icy conditions
+    icy_sessions: u32,
+    /// Number of sessions with loading over a sharp edge
+    sharp_edge_sessions: u32,
+    /// Number of falls held since the last inspection
+    falls_since_inspection: i32,
+    /// Whether the usage since the last inspection requires a new inspection
+    requires_inspection: bool,
+}
+
+impl UsageSummary {
+    fn from_events(events: &[Event]) -> Self {
+        Event::effective_history(events).into_iter().fold(
+            Self::default(),
+            |mut summary, (_, data)| {
+                if let EventData::Used {
+                    falls,
+                    worst_fall_factor,
+                    conditions,
+                    ..
+                } = data
+                {
+                    summary.sessions += 1;
+                    summary.falls = summary.falls.saturating_add(*falls);
+                    summary.worst_fall_factor = summary
+                        .worst_fall_factor
+                        .into_iter()
+                        .chain(*worst_fall_factor)
+                        .max_by(f32::total_cmp);
+                    for condition in conditions {
+                        match condition {
+                            UsageCondition::Wet => summary.wet_sessions += 1,
+                            UsageCondition::Icy => summary.icy_sessions += 1,
+                            UsageCondition::SharpEdge => summary.sharp_edge_sessions += 1,
+                        }
+                    }
+                }
+                summary
+            },
+        )
+    }
+}
+
 #[derive(serde::Serialize, ts_rs::TS)]
 #[ts(export)]
 pub struct ItemEvent {
@@ -42,6 +120,8 @@ pub struct ItemEvent {
     ts: chrono::DateTime<Utc>,
     /// Details of the event
     data: EventData,
+    /// Id of the latest event voiding or amending this one
+    corrected_by: Option<i64>,
 }
 
 impl From<Event> for ItemEvent {
@@ -50,14 +130,41 @@ impl From<Event> for ItemEvent {
             id: value.id,
             ts: chrono::DateTime::from_naive_utc_and_offset(value.ts, Utc),
             data: value.data,
+            corrected_by: None,
         }
     }
 }
 
-impl From<(ItemModel, Vec<ItemTag>, Vec<Event>)> for ItemDetails {
-    fn from(value: (ItemModel, Vec<ItemTag>, Vec<Event>)) -> Self {
+impl From<(ItemModel, Vec<(ItemTag, TagModel)>, Vec<Event>)> for ItemDetails {
+    fn from(value: (ItemModel, Vec<(ItemTag, TagModel)>, Vec<Event>)) -> Self {
+        let end_of_life = value.0.end_of_life(value.1.iter().map(|(_, tag)| tag));
+        let corrected_by: HashMap<i64, i64> = value
+            .2
+            .iter()
+            .filter_map(|event| Some((event.data.corrected_event()?, event.id)))
+            .collect();
+        let usage = UsageSummary {
+            falls_since_inspection: value.0.falls_since_inspection,
+            requires_inspection: value
+                .0
+                .usage_requires_inspection(value.1.iter().map(|(_, tag)| tag)),
+            ..UsageSummary::from_events(&value.2)
+        };
         Self {
             id: value.0.id,
+            next_inspection_due: value
+                .0
+                .next_inspection_due(value.1.iter().map(|(_, tag)| tag)),
+            inspection_after_falls: value.0.inspection_after_falls,
+            inspection_after_fall_factor: value.0.inspection_after_fall_factor,
+            end_of_life,
+            past_end_of_life: end_of_life.is_some_and(|end_of_life| end_of_life <= Utc::now()),
+            archived_ts: value
+                .0
+                .archived_ts
+                .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
+            max_lifetime_years: value.0.max_lifetime_years,
+            max_service_years: value.0.max_service_years,
             name: value.0.name,
             serial_number: value.0.serial_number,
             inspection_period_days: value
@@ -67,9 +174,22 @@ impl From<(ItemModel, Vec<ItemTag>, Vec<Event>)> for ItemDetails {
             tags: value
                 .1
                 .into_iter()
-                .map(|item_tag| item_tag.tag_id)
+                .map(|(item_tag, _)| item_tag.tag_id)
+                .collect(),
+            status: value.0.status,
+            last_event_ts: value
+                .0
+                .last_event_ts
+                .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
+            usage,
+            events: value
+                .2
+                .into_iter()
+                .map(|event| ItemEvent {
+                    corrected_by: corrected_by.get(&event.id).copied(),
+                    ..event.into()
+                })
                 .collect(),
-            events: value.2.into_iter().map(|event| event.into()).collect(),
         }
     }
 }
@@ -87,11 +207,11 @@ pub async fn handler(
     let tags = ItemTag::belonging_to(&item)
         .inner_join(tags::table)
         .order_by(tags::name.asc())
-        .select(items_tags::all_columns)
-        .get_results::<ItemTag>(&mut conn)
+        .select((ItemTag::as_select(), TagModel::as_select()))
+        .get_results::<(ItemTag, TagModel)>(&mut conn)
         .await?;
     let events = Event::belonging_to(&item)
-        .order_by(events::ts.asc())
+        .order_by((events::ts.asc(), events::id.asc()))
         .get_results::<Event>(&mut conn)
         .await?;
 
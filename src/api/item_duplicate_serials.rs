@@ -0,0 +1,41 @@
+use axum::{extract::State, Json};
+use diesel::{
+    sql_types::{Array, BigInt, Varchar},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+
+#[derive(serde::Serialize, ts_rs::TS, QueryableByName)]
+#[ts(export)]
+pub struct DuplicateSerial {
+    /// Name of the items
+    #[diesel(sql_type = Varchar)]
+    name: String,
+    /// Serial number shared by the items
+    #[diesel(sql_type = Varchar)]
+    serial_number: String,
+    /// Ids of the items sharing the serial number
+    #[diesel(sql_type = Array<BigInt>)]
+    items: Vec<i64>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+) -> ApiResult<Json<Vec<DuplicateSerial>>> {
+    let mut conn = state.database.get().await?;
+    let duplicates = diesel::sql_query(
+        "SELECT name, serial_number, array_agg(id ORDER BY id) AS items \
+        FROM items \
+        WHERE serial_number IS NOT NULL AND archived_ts IS NULL \
+        GROUP BY name, serial_number \
+        HAVING COUNT(*) > 1 \
+        ORDER BY name, serial_number",
+    )
+    .get_results::<DuplicateSerial>(&mut conn)
+    .await?;
+
+    Ok(Json(duplicates))
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::event::{Event, EventData};
+
+use super::{ApiResult, Application, AuthenticatedUser, LendItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct FindItem {
+    /// Person who found the item
+    finder: String,
+    /// Where the item was found
+    location: String,
+    /// Time the item was found in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(FindItem {
+        finder,
+        location,
+        ts,
+    }): Json<FindItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Found {
+            finder,
+            location,
+            validator: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
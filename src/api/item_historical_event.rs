@@ -0,0 +1,83 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::QueryDsl as _;
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use crate::{
+    models::{
+        event::{Event, EventData},
+        member::Member,
+    },
+    schema::members,
+};
+
+use super::{
+    item_details::ItemEvent, Administrate, ApiError, ApiResult, Application, AuthenticatedUser,
+};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct InsertHistoricalEvent {
+    /// Time the event really happened in UTC, can be before other events
+    ts: chrono::DateTime<Utc>,
+    /// Details of the event, with the people who really took part in it
+    data: EventData,
+}
+
+/// Take the name of the borrower from the members, the way live borrows do
+pub(super) async fn fill_borrower(
+    conn: &mut diesel_async::AsyncPgConnection,
+    mut data: EventData,
+) -> ApiResult<EventData> {
+    if let EventData::Borrowed {
+        borrower_id,
+        borrower,
+        ..
+    } = &mut data
+    {
+        let member = members::table
+            .find(*borrower_id)
+            .get_result::<Member>(conn)
+            .await?;
+        *borrower = member.name;
+    }
+    Ok(data)
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<Administrate>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(InsertHistoricalEvent { ts, data }): Json<InsertHistoricalEvent>,
+) -> ApiResult<Json<ItemEvent>> {
+    // corrections and audits have their own endpoints
+    if !data.kind().is_lifecycle() {
+        return Err(ApiError::NotLifecycleEvent(data.kind()));
+    }
+    let mut conn = state.database.get().await?;
+    let event = conn
+        .transaction(|conn| {
+            async move {
+                let data = fill_borrower(conn, data).await?;
+                let event = Event::insert_historical_event(conn, item_id, ts, data).await?;
+                // the event keeps who did the work, the audit keeps who logged it late
+                Event::insert_audit_event(
+                    conn,
+                    item_id,
+                    Utc::now(),
+                    EventData::Recorded {
+                        target_event_id: event.id,
+                        recorder: auth.claims.login,
+                    },
+                )
+                .await?;
+                Ok::<_, ApiError>(event)
+            }
+            .scope_boxed()
+        })
+        .await?;
+    Ok(Json(event.into()))
+}
@@ -1,11 +1,26 @@
-use axum::{extract::State, Json};
-use diesel::{BelongingToDsl as _, GroupedBy as _};
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{
+    dsl::sql,
+    pg::Pg,
+    sql_types::{Array, BigInt, Bool, Text},
+    BoolExpressionMethods as _, ExpressionMethods as _, PgTextExpressionMethods as _,
+    QueryDsl as _, SelectableHelper as _,
+};
 use diesel_async::RunQueryDsl as _;
 
 use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
 use crate::{
-    models::{item::Item as ItemModel, tag::ItemTag},
-    schema,
+    models::{
+        item::{Item as ItemModel, ItemStatus},
+        tag::{ItemTag, Tag as TagModel},
+    },
+    schema::{items, items_tags, tags},
 };
 
 #[derive(serde::Serialize, ts_rs::TS)]
@@ -21,45 +36,243 @@ pub struct Item {
     serial_number: Option<String>,
     /// Ids of all tags associated to this item
     tags: Vec<i64>,
+    /// Current status of the item
+    status: ItemStatus,
+    /// Time of the last event of the item
+    last_event_ts: Option<chrono::DateTime<Utc>>,
+    /// Date when the next inspection is due
+    next_inspection_due: Option<chrono::DateTime<Utc>>,
+    /// Optional maximum lifetime from manufacture, overrides the one of the tags
+    max_lifetime_years: Option<i32>,
+    /// Optional maximum lifetime from put into service, overrides the one of the tags
+    max_service_years: Option<i32>,
+    /// Optional number of falls after which the item must be inspected, overrides the one of the tags
+    inspection_after_falls: Option<i32>,
+    /// Optional fall factor after which the item must be inspected, overrides the one of the tags
+    inspection_after_fall_factor: Option<f32>,
+    /// Date after which the item must not be used anymore
+    end_of_life: Option<chrono::DateTime<Utc>>,
+    /// Whether the item is past its end of life
+    past_end_of_life: bool,
+    /// Time the item was archived, unset for active items
+    archived_ts: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct ItemFilter {
+    /// Only return items with this status
+    status: Option<ItemStatus>,
+    /// Also return archived items
+    archived: Option<bool>,
+    /// Only return items with this tag
+    tag: Option<i64>,
+    /// Only return items whose name or serial number contains this text
+    q: Option<String>,
+    /// Key to sort items by, defaults to the name
+    sort: Option<ItemSort>,
+    /// Sort items in descending order
+    descending: Option<bool>,
+    /// Id of the last item of the previous page
+    cursor: Option<i64>,
+    /// Maximum number of items to return, defaults to 100
+    limit: Option<i64>,
+}
+
+#[derive(serde::Deserialize, ts_rs::TS, Clone, Copy)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemSort {
+    /// Sort by id, ie. creation order
+    Id,
+    /// Sort by name
+    Name,
+    /// Sort by serial number, items without one first
+    SerialNumber,
+    /// Sort by status
+    Status,
+    /// Sort by time of the last event, items without events first
+    LastEventTs,
+}
+
+impl ItemSort {
+    /// SQL expression of the sort key, never null so that it can be compared
+    fn sql(&self) -> &'static str {
+        match self {
+            ItemSort::Id => "items.id",
+            ItemSort::Name => "items.name",
+            ItemSort::SerialNumber => "COALESCE(items.serial_number, '')",
+            ItemSort::Status => "items.status",
+            ItemSort::LastEventTs => "COALESCE(items.last_event_ts, '-infinity'::timestamp)",
+        }
+    }
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct ItemPage {
+    /// Items of the page
+    items: Vec<Item>,
+    /// Number of items matching the filter, on all pages
+    total: i64,
+    /// Cursor of the next page, unset on the last page
+    next_cursor: Option<i64>,
 }
 
-impl From<(ItemModel, Vec<ItemTag>)> for Item {
-    fn from(value: (ItemModel, Vec<ItemTag>)) -> Self {
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 1000;
+
+impl Item {
+    pub(crate) fn new<'a>(
+        item: ItemModel,
+        tags: impl IntoIterator<Item = &'a TagModel> + Clone,
+    ) -> Self {
+        let end_of_life = item.end_of_life(tags.clone());
         Self {
-            id: value.0.id,
-            name: value.0.name,
-            serial_number: value.0.serial_number,
-            inspection_period_days: value
-                .0
+            id: item.id,
+            next_inspection_due: item.next_inspection_due(tags.clone()),
+            inspection_after_falls: item.inspection_after_falls,
+            inspection_after_fall_factor: item.inspection_after_fall_factor,
+            end_of_life,
+            past_end_of_life: end_of_life.is_some_and(|end_of_life| end_of_life <= Utc::now()),
+            archived_ts: item
+                .archived_ts
+                .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
+            max_lifetime_years: item.max_lifetime_years,
+            max_service_years: item.max_service_years,
+            name: item.name,
+            serial_number: item.serial_number,
+            inspection_period_days: item
                 .inspection_period_days
                 .map(|pg_interval| pg_interval.days),
-            tags: value
-                .1
-                .into_iter()
-                .map(|item_tag| item_tag.tag_id)
-                .collect(),
+            tags: tags.into_iter().map(|tag| tag.id).collect(),
+            status: item.status,
+            last_event_ts: item
+                .last_event_ts
+                .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
         }
     }
 }
 
+impl From<(ItemModel, Vec<(ItemTag, TagModel)>)> for Item {
+    fn from(value: (ItemModel, Vec<(ItemTag, TagModel)>)) -> Self {
+        Self::new(value.0, value.1.iter().map(|(_, tag)| tag))
+    }
+}
+
+fn filter_items<'a, ST: 'a>(
+    mut query: items::BoxedQuery<'a, Pg, ST>,
+    filter: &ItemFilter,
+) -> items::BoxedQuery<'a, Pg, ST> {
+    if let Some(status) = filter.status {
+        query = query.filter(items::status.eq(status));
+    }
+    if !filter.archived.unwrap_or(false) {
+        query = query.filter(items::archived_ts.is_null());
+    }
+    if let Some(tag_id) = filter.tag {
+        query = query.filter(
+            items::id.eq_any(
+                items_tags::table
+                    .filter(items_tags::tag_id.eq(tag_id))
+                    .select(items_tags::item_id),
+            ),
+        );
+    }
+    if let Some(q) = &filter.q {
+        let pattern = format!(
+            "%{}%",
+            q.replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        query = query.filter(
+            items::name
+                .ilike(pattern.clone())
+                .or(items::serial_number.ilike(pattern)),
+        );
+    }
+    query
+}
+
 pub async fn handler(
     _auth: AuthenticatedUser<NoPermission>,
     state: State<Application>,
-) -> ApiResult<Json<Vec<Item>>> {
+    Query(filter): Query<ItemFilter>,
+) -> ApiResult<Json<ItemPage>> {
+    let sort = filter.sort.unwrap_or(ItemSort::Name);
+    let (direction, comparison) = match filter.descending.unwrap_or(false) {
+        false => ("ASC", ">"),
+        true => ("DESC", "<"),
+    };
+    let limit = filter
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
     let mut conn = state.database.get().await?;
-    let items = schema::items::table
-        .get_results::<ItemModel>(&mut conn)
+    let total = filter_items(items::table.count().into_boxed(), &filter)
+        .get_result::<i64>(&mut conn)
         .await?;
-    let tags = ItemTag::belonging_to(&items)
-        .get_results::<ItemTag>(&mut conn)
+
+    // tags are grouped by the database, only their ids are returned with each item
+    let mut query = filter_items(
+        items::table
+            .select((
+                ItemModel::as_select(),
+                sql::<Array<BigInt>>(
+                    "ARRAY(SELECT items_tags.tag_id FROM items_tags \
+                    WHERE items_tags.item_id = items.id ORDER BY items_tags.tag_id)",
+                ),
+            ))
+            .into_boxed(),
+        &filter,
+    )
+    .order_by(sql::<Text>(&format!(
+        "{} {direction}, items.id {direction}",
+        sort.sql()
+    )));
+    if let Some(cursor) = filter.cursor {
+        // keyset pagination, resuming after the sort key of the cursor item
+        query = query.filter(
+            sql::<Bool>(&format!(
+                "({key}, items.id) {comparison} ((SELECT {key} FROM items WHERE items.id = ",
+                key = sort.sql()
+            ))
+            .bind::<BigInt, _>(cursor)
+            .sql("), ")
+            .bind::<BigInt, _>(cursor)
+            .sql(")"),
+        );
+    }
+    let items = query
+        .limit(limit)
+        .get_results::<(ItemModel, Vec<i64>)>(&mut conn)
+        .await?;
+
+    let tag_ids = items
+        .iter()
+        .flat_map(|(_, tag_ids)| tag_ids.iter().copied())
+        .collect::<Vec<_>>();
+    let tags = tags::table
+        .filter(tags::id.eq_any(tag_ids))
+        .get_results::<TagModel>(&mut conn)
         .await?
-        .grouped_by(&items);
+        .into_iter()
+        .map(|tag| (tag.id, tag))
+        .collect::<HashMap<_, _>>();
 
-    Ok(Json(
-        items
+    let next_cursor = (items.len() as i64 == limit)
+        .then(|| items.last().map(|(item, _)| item.id))
+        .flatten();
+    Ok(Json(ItemPage {
+        items: items
             .into_iter()
-            .zip(tags)
-            .map(|(item_model, item_tags)| (item_model, item_tags).into())
-            .collect::<Vec<Item>>(),
-    ))
+            .map(|(item, tag_ids)| {
+                Item::new(item, tag_ids.iter().filter_map(|tag_id| tags.get(tag_id)))
+            })
+            .collect(),
+        total,
+        next_cursor,
+    }))
 }
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::event::{Event, EventData, RemovalReason};
+
+use super::{ApiResult, Application, AuthenticatedUser, LendItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct LoseItem {
+    /// Why the item is considered lost
+    reason: RemovalReason,
+    /// Optional comment about the loss
+    comment: Option<String>,
+    /// Time of the loss in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(LoseItem {
+        reason,
+        comment,
+        ts,
+    }): Json<LoseItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Lost {
+            reason,
+            comment,
+            validator: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
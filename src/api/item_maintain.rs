@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::event::{Event, EventData, MaintenanceKind};
+
+use super::{ApiResult, Application, AuthenticatedUser, MaintainItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct MaintainItem {
+    /// Kind of maintenance
+    maintenance: MaintenanceKind,
+    /// Optional comment about the maintenance
+    comment: Option<String>,
+    /// Time of the maintenance in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<MaintainItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(MaintainItem {
+        maintenance,
+        comment,
+        ts,
+    }): Json<MaintainItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Maintained {
+            maintenance,
+            performer: auth.claims.login,
+            comment,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::{ExpressionMethods as _, QueryDsl as _};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use super::{
+    Administrate, ApiError, ApiResult, Application, AuthenticatedUser, ClaimPermission as _,
+    ManageItems,
+};
+use crate::{models::item::Item as ItemModel, schema::*};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct PurgeItem {
+    /// Name of the item, to confirm it must be deleted with its whole history
+    confirm_name: String,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(PurgeItem { confirm_name }): Json<PurgeItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    conn.transaction(|conn| {
+        async move {
+            let item = items::table
+                .find(item_id)
+                .for_update()
+                .get_result::<ItemModel>(conn)
+                .await?;
+            if item.name != confirm_name {
+                return Err(ApiError::PurgeNotConfirmed(item_id));
+            }
+            // the safety history of an item must be kept, unless an administrator decides otherwise
+            let events = events::table
+                .filter(events::item_id.eq(item_id))
+                .count()
+                .get_result::<i64>(conn)
+                .await?;
+            if events > 0 && !Administrate::check(&auth.claims) {
+                return Err(ApiError::ItemHasHistory(item_id));
+            }
+            diesel::delete(items::table.find(item_id))
+                .execute(conn)
+                .await?;
+            Ok(Json(()))
+        }
+        .scope_boxed()
+    })
+    .await
+}
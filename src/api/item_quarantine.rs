@@ -0,0 +1,39 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::event::{Event, EventData};
+
+use super::{ApiResult, Application, AuthenticatedUser, InspectItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct QuarantineItem {
+    /// Why the item is quarantined
+    reason: String,
+    /// Time of the quarantine in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<InspectItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(QuarantineItem { reason, ts }): Json<QuarantineItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Quarantined {
+            reason,
+            validator: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
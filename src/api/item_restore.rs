@@ -0,0 +1,26 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::NaiveDateTime;
+use diesel::{ExpressionMethods as _, QueryDsl as _};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, ManageItems};
+use crate::schema::*;
+
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let restored = diesel::update(items::table.find(item_id))
+        .set(items::archived_ts.eq(None::<NaiveDateTime>))
+        .execute(&mut conn)
+        .await?;
+    if restored == 0 {
+        return Err(diesel::result::Error::NotFound.into());
+    }
+    Ok(Json(()))
+}
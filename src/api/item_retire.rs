@@ -0,0 +1,50 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::event::{Disposal, Event, EventData, RemovalReason};
+
+use super::{ApiResult, Application, AuthenticatedUser, ManageItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct RetireItem {
+    /// Why the item is retired
+    reason: RemovalReason,
+    /// What was done with the item
+    disposal: Disposal,
+    /// Optional comment about the retirement
+    comment: Option<String>,
+    /// Time of the retirement in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(RetireItem {
+        reason,
+        disposal,
+        comment,
+        ts,
+    }): Json<RetireItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Retired {
+            reason,
+            disposal,
+            comment,
+            validator: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
@@ -0,0 +1,36 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::event::{Event, EventData};
+
+use super::{ApiResult, Application, AuthenticatedUser, LendItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct ReturnItem {
+    /// Time of the return in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(ReturnItem { ts }): Json<ReturnItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Returned {
+            validator: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
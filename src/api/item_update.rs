@@ -0,0 +1,221 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{
+    data_types::PgInterval, BelongingToDsl as _, ExpressionMethods as _, QueryDsl as _,
+    SelectableHelper as _,
+};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use crate::{
+    models::{
+        event::{Event, EventData, FieldChange},
+        item::Item as ItemModel,
+        tag::{InsertItemTag, ItemTag, Tag as TagModel},
+    },
+    schema::*,
+};
+
+use super::{
+    item_create::duplicate_serials, item_list::Item, ApiError, ApiResult, Application,
+    AuthenticatedUser, ManageItems,
+};
+
+/// Fields left out are kept as they are, `null` clears the optional ones
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct UpdateItem {
+    /// New name of the item
+    #[ts(optional)]
+    name: Option<String>,
+    /// New inspection period
+    #[serde(default, deserialize_with = "present")]
+    #[ts(optional)]
+    inspection_period_days: Option<Option<i32>>,
+    /// New serial number
+    #[serde(default, deserialize_with = "present")]
+    #[ts(optional)]
+    serial_number: Option<Option<String>>,
+    /// Ids of all tags associated to the item
+    #[ts(optional)]
+    tags: Option<Vec<i64>>,
+    /// Refuse a new serial number already used by another item with the same name
+    reject_duplicate_serial: Option<bool>,
+}
+
+/// Tell a field set to `null` from a field left out, which stays `None`
+fn present<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: serde::Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    <Option<T> as serde::Deserialize>::deserialize(deserializer).map(Some)
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct UpdatedItem {
+    /// The updated item
+    item: Item,
+    /// Ids of other items with the same name and serial number, if they changed
+    duplicate_serials: Vec<i64>,
+}
+
+fn tag_names(tags: &[(ItemTag, TagModel)]) -> Option<String> {
+    let mut names = tags
+        .iter()
+        .map(|(_, tag)| tag.name.as_str())
+        .collect::<Vec<_>>();
+    names.sort();
+    (!names.is_empty()).then(|| names.join(", "))
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(UpdateItem {
+        name,
+        inspection_period_days,
+        serial_number,
+        mut tags,
+        reject_duplicate_serial,
+    }): Json<UpdateItem>,
+) -> ApiResult<Json<UpdatedItem>> {
+    if let Some(days) = inspection_period_days.flatten().filter(|days| *days < 0) {
+        return Err(ApiError::InvalidInspectionPeriod(days));
+    }
+    if let Some(tags) = &mut tags {
+        tags.sort_unstable();
+        tags.dedup();
+    }
+    let mut conn = state.database.get().await?;
+    let (item, item_tags, duplicate_serials) = conn
+        .transaction(|conn| {
+            async move {
+                let item = items::table
+                    .find(item_id)
+                    .for_update()
+                    .get_result::<ItemModel>(conn)
+                    .await?;
+                if let Some(tags) = &tags {
+                    let found_tags = tags::table
+                        .filter(tags::id.eq_any(tags))
+                        .select(tags::id)
+                        .get_results::<i64>(conn)
+                        .await?;
+                    if let Some(tag_id) = tags.iter().find(|tag_id| !found_tags.contains(tag_id)) {
+                        return Err(ApiError::UnknownTag(*tag_id));
+                    }
+                }
+                let old_period = item
+                    .inspection_period_days
+                    .as_ref()
+                    .map(|pg_interval| pg_interval.days);
+                let name = name.unwrap_or_else(|| item.name.clone());
+                let serial_number = serial_number.unwrap_or_else(|| item.serial_number.clone());
+                let inspection_period_days = inspection_period_days.unwrap_or(old_period);
+                let duplicate_serials = if item.name != name || item.serial_number != serial_number
+                {
+                    duplicate_serials(conn, &name, serial_number.as_deref(), Some(item_id)).await?
+                } else {
+                    vec![]
+                };
+                if !duplicate_serials.is_empty() && reject_duplicate_serial.unwrap_or(false) {
+                    return Err(ApiError::DuplicateSerial(
+                        name,
+                        serial_number.unwrap_or_default(),
+                    ));
+                }
+                let old_tags = ItemTag::belonging_to(&item)
+                    .inner_join(tags::table)
+                    .select((ItemTag::as_select(), TagModel::as_select()))
+                    .get_results::<(ItemTag, TagModel)>(conn)
+                    .await?;
+
+                let mut changes = vec![];
+                if item.name != name {
+                    changes.push(FieldChange {
+                        field: "name".to_owned(),
+                        old: Some(item.name.clone()),
+                        new: Some(name.clone()),
+                    });
+                }
+                if item.serial_number != serial_number {
+                    changes.push(FieldChange {
+                        field: "serial_number".to_owned(),
+                        old: item.serial_number.clone(),
+                        new: serial_number.clone(),
+                    });
+                }
+                if old_period != inspection_period_days {
+                    changes.push(FieldChange {
+                        field: "inspection_period_days".to_owned(),
+                        old: old_period.map(|days| days.to_string()),
+                        new: inspection_period_days.map(|days| days.to_string()),
+                    });
+                }
+
+                let item = diesel::update(items::table.find(item_id))
+                    .set((
+                        items::name.eq(name),
+                        items::serial_number.eq(serial_number),
+                        items::inspection_period_days
+                            .eq(inspection_period_days.map(PgInterval::from_days)),
+                    ))
+                    .returning(items::all_columns)
+                    .get_result::<ItemModel>(conn)
+                    .await?;
+
+                if let Some(tags) = tags {
+                    diesel::delete(items_tags::table.filter(items_tags::item_id.eq(item_id)))
+                        .execute(conn)
+                        .await?;
+                    diesel::insert_into(items_tags::table)
+                        .values(
+                            tags.into_iter()
+                                .map(|tag_id| InsertItemTag { item_id, tag_id })
+                                .collect::<Vec<_>>(),
+                        )
+                        .execute(conn)
+                        .await?;
+                }
+                let item_tags = ItemTag::belonging_to(&item)
+                    .inner_join(tags::table)
+                    .select((ItemTag::as_select(), TagModel::as_select()))
+                    .get_results::<(ItemTag, TagModel)>(conn)
+                    .await?;
+                if tag_names(&old_tags) != tag_names(&item_tags) {
+                    changes.push(FieldChange {
+                        field: "tags".to_owned(),
+                        old: tag_names(&old_tags),
+                        new: tag_names(&item_tags),
+                    });
+                }
+
+                if !changes.is_empty() {
+                    Event::insert_audit_event(
+                        conn,
+                        item_id,
+                        Utc::now(),
+                        EventData::Edited {
+                            changes,
+                            editor: auth.claims.login,
+                        },
+                    )
+                    .await?;
+                }
+
+                Ok::<_, ApiError>((item, item_tags, duplicate_serials))
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(UpdatedItem {
+        item: (item, item_tags).into(),
+        duplicate_serials,
+    }))
+}
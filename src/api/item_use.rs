@@ -0,0 +1,58 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::event::{Event, EventData, UsageCondition};
+
+use super::{ApiResult, Application, AuthenticatedUser, LendItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct UseItem {
+    /// Day of the session
+    session_date: chrono::NaiveDate,
+    /// Number of falls held by the item
+    falls: u32,
+    /// Worst fall factor held by the item between 0 and 2, if known
+    worst_fall_factor: Option<f32>,
+    /// Conditions the item was used in
+    conditions: Vec<UsageCondition>,
+    /// Optional comment about the session
+    comment: Option<String>,
+    /// Time of the report in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(item_id): Path<i64>,
+    Json(UseItem {
+        session_date,
+        falls,
+        worst_fall_factor,
+        conditions,
+        comment,
+        ts,
+    }): Json<UseItem>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    Event::insert_event(
+        &mut conn,
+        item_id,
+        ts,
+        EventData::Used {
+            session_date,
+            falls,
+            worst_fall_factor,
+            conditions,
+            comment,
+            reporter: auth.claims.login,
+        },
+    )
+    .await?;
+    Ok(Json(()))
+}
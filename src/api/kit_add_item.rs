@@ -0,0 +1,49 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use crate::{
+    models::kit::{InsertKitItem, Kit as KitModel},
+    schema::kits_items,
+};
+
+use super::{
+    kit_list::{load_kit, Kit},
+    ApiResult, Application, AuthenticatedUser, ManageItems,
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct AddKitItem {
+    /// Id of the item to add to the kit
+    item_id: i64,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(kit_id): Path<i64>,
+    Json(AddKitItem { item_id }): Json<AddKitItem>,
+) -> ApiResult<Json<Kit>> {
+    let mut conn = state.database.get().await?;
+    let kit = conn
+        .transaction(|conn| {
+            async move {
+                // only open kits can receive items
+                KitModel::open_item_ids(conn, kit_id).await?;
+                KitModel::check_free_items(conn, kit_id, &[item_id]).await?;
+                diesel::insert_into(kits_items::table)
+                    .values(InsertKitItem { kit_id, item_id })
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .await?;
+                load_kit(conn, kit_id).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(kit))
+}
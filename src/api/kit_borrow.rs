@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use crate::{
+    models::{
+        event::{Event, EventData},
+        kit::Kit,
+        member::Member,
+    },
+    schema::members,
+};
+
+use super::{
+    loan_create::{batch_response, ItemOutcome},
+    ApiResult, Application, AuthenticatedUser, LendItems,
+};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct BorrowKit {
+    /// Member who borrows the kit
+    borrower_id: i64,
+    /// Time the kit is expected back in UTC
+    due_back: Option<chrono::DateTime<Utc>>,
+    /// Time of the borrow in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(kit_id): Path<i64>,
+    Json(BorrowKit {
+        borrower_id,
+        due_back,
+        ts,
+    }): Json<BorrowKit>,
+) -> ApiResult<(StatusCode, Json<Vec<ItemOutcome>>)> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    let item_ids = Kit::open_item_ids(&mut conn, kit_id).await?;
+    let borrower = members::table
+        .find(borrower_id)
+        .get_result::<Member>(&mut conn)
+        .await?;
+    let result = Event::insert_events(
+        &mut conn,
+        &item_ids,
+        ts,
+        EventData::Borrowed {
+            borrower_id: borrower.id,
+            borrower: borrower.name,
+            validator: auth.claims.login,
+            due_back,
+        },
+    )
+    .await;
+    batch_response(&item_ids, result)
+}
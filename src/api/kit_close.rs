@@ -0,0 +1,28 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{ExpressionMethods as _, QueryDsl as _};
+use diesel_async::RunQueryDsl as _;
+
+use super::{
+    kit_list::{load_kit, Kit},
+    ApiResult, Application, AuthenticatedUser, ManageItems,
+};
+use crate::schema::kits;
+
+/// Take a kit apart, its items can then join other kits
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(kit_id): Path<i64>,
+) -> ApiResult<Json<Kit>> {
+    let mut conn = state.database.get().await?;
+    diesel::update(kits::table.find(kit_id).filter(kits::closed_ts.is_null()))
+        .set(kits::closed_ts.eq(Utc::now().naive_utc()))
+        .execute(&mut conn)
+        .await?;
+
+    Ok(Json(load_kit(&mut conn, kit_id).await?))
+}
@@ -0,0 +1,59 @@
+use axum::{extract::State, Json};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use crate::{
+    models::kit::{InsertKit, InsertKitItem, Kit as KitModel},
+    schema::{kits, kits_items},
+};
+
+use super::{
+    kit_list::{load_kit, Kit},
+    ApiResult, Application, AuthenticatedUser, ManageItems,
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct CreateKit {
+    /// Name of the kit to create
+    name: String,
+    /// Ids of the items of the kit
+    items: Vec<i64>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Json(CreateKit { name, mut items }): Json<CreateKit>,
+) -> ApiResult<Json<Kit>> {
+    items.sort_unstable();
+    items.dedup();
+    let mut conn = state.database.get().await?;
+    let kit = conn
+        .transaction(|conn| {
+            async move {
+                let kit = diesel::insert_into(kits::table)
+                    .values(InsertKit { name })
+                    .returning(kits::all_columns)
+                    .get_result::<KitModel>(conn)
+                    .await?;
+                KitModel::check_free_items(conn, kit.id, &items).await?;
+                diesel::insert_into(kits_items::table)
+                    .values(
+                        items
+                            .into_iter()
+                            .map(|item_id| InsertKitItem {
+                                kit_id: kit.id,
+                                item_id,
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .execute(conn)
+                    .await?;
+                load_kit(conn, kit.id).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(kit))
+}
@@ -0,0 +1,54 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::{
+    event::{Event, EventData, InspectionResult},
+    kit::Kit,
+};
+
+use super::{
+    loan_create::{batch_response, ItemOutcome},
+    ApiResult, Application, AuthenticatedUser, InspectItems,
+};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct InspectKit {
+    /// Inspection result, applied to every item of the kit
+    result: InspectionResult,
+    /// Comment of inspector
+    comment: Option<String>,
+    /// Time of the inspection in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<InspectItems>,
+    state: State<Application>,
+    Path(kit_id): Path<i64>,
+    Json(InspectKit {
+        result,
+        comment,
+        ts,
+    }): Json<InspectKit>,
+) -> ApiResult<(StatusCode, Json<Vec<ItemOutcome>>)> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    let item_ids = Kit::open_item_ids(&mut conn, kit_id).await?;
+    let result = Event::insert_events(
+        &mut conn,
+        &item_ids,
+        ts,
+        EventData::Inspected {
+            inspector: auth.claims.login,
+            result,
+            comment,
+        },
+    )
+    .await;
+    batch_response(&item_ids, result)
+}
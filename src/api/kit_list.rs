@@ -0,0 +1,102 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{
+    BelongingToDsl as _, ExpressionMethods as _, GroupedBy as _, QueryDsl as _,
+    SelectableHelper as _,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{
+    models::{
+        item::ItemStatus,
+        kit::{Kit as KitModel, KitItem},
+    },
+    schema::{items, kits},
+};
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct Kit {
+    /// Id of the kit
+    id: i64,
+    /// Name of the kit
+    name: String,
+    /// Time the kit was taken apart, unset while the kit is open
+    closed_ts: Option<chrono::DateTime<Utc>>,
+    /// Ids of all items of the kit
+    items: Vec<i64>,
+    /// Status shared by all items of the kit, unset if they differ
+    status: Option<ItemStatus>,
+}
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct KitFilter {
+    /// Also return kits which were taken apart
+    closed: Option<bool>,
+}
+
+impl From<(KitModel, Vec<(KitItem, ItemStatus)>)> for Kit {
+    fn from(value: (KitModel, Vec<(KitItem, ItemStatus)>)) -> Self {
+        Self {
+            id: value.0.id,
+            name: value.0.name,
+            closed_ts: value
+                .0
+                .closed_ts
+                .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
+            status: KitModel::status(value.1.iter().map(|(_, status)| *status)),
+            items: value
+                .1
+                .into_iter()
+                .map(|(kit_item, _)| kit_item.item_id)
+                .collect(),
+        }
+    }
+}
+
+pub(crate) async fn load_kit(
+    conn: &mut diesel_async::AsyncPgConnection,
+    kit_id: i64,
+) -> ApiResult<Kit> {
+    let kit = kits::table
+        .find(kit_id)
+        .get_result::<KitModel>(conn)
+        .await?;
+    let kit_items = KitItem::belonging_to(&kit)
+        .inner_join(items::table)
+        .select((KitItem::as_select(), items::status))
+        .get_results::<(KitItem, ItemStatus)>(conn)
+        .await?;
+    Ok((kit, kit_items).into())
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Query(filter): Query<KitFilter>,
+) -> ApiResult<Json<Vec<Kit>>> {
+    let mut conn = state.database.get().await?;
+    let mut query = kits::table.order_by(kits::name.asc()).into_boxed();
+    if !filter.closed.unwrap_or(false) {
+        query = query.filter(kits::closed_ts.is_null());
+    }
+    let kits = query.get_results::<KitModel>(&mut conn).await?;
+    let kit_items = KitItem::belonging_to(&kits)
+        .inner_join(items::table)
+        .select((KitItem::as_select(), items::status))
+        .get_results::<(KitItem, ItemStatus)>(&mut conn)
+        .await?
+        .grouped_by(&kits);
+
+    Ok(Json(
+        kits.into_iter()
+            .zip(kit_items)
+            .map(|(kit_model, kit_items)| (kit_model, kit_items).into())
+            .collect::<Vec<Kit>>(),
+    ))
+}
@@ -0,0 +1,29 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::{ExpressionMethods as _, QueryDsl as _};
+use diesel_async::RunQueryDsl as _;
+
+use super::{
+    kit_list::{load_kit, Kit},
+    ApiResult, Application, AuthenticatedUser, ManageItems,
+};
+use crate::schema::kits_items;
+
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path((kit_id, item_id)): Path<(i64, i64)>,
+) -> ApiResult<Json<Kit>> {
+    let mut conn = state.database.get().await?;
+    diesel::delete(
+        kits_items::table
+            .filter(kits_items::kit_id.eq(kit_id))
+            .filter(kits_items::item_id.eq(item_id)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    Ok(Json(load_kit(&mut conn, kit_id).await?))
+}
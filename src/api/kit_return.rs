@@ -0,0 +1,44 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+
+use crate::models::{
+    event::{Event, EventData},
+    kit::Kit,
+};
+
+use super::{
+    loan_create::{batch_response, ItemOutcome},
+    ApiResult, Application, AuthenticatedUser, LendItems,
+};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct ReturnKit {
+    /// Time of the return in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(kit_id): Path<i64>,
+    Json(ReturnKit { ts }): Json<ReturnKit>,
+) -> ApiResult<(StatusCode, Json<Vec<ItemOutcome>>)> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    let item_ids = Kit::open_item_ids(&mut conn, kit_id).await?;
+    let result = Event::insert_events(
+        &mut conn,
+        &item_ids,
+        ts,
+        EventData::Returned {
+            validator: auth.claims.login,
+        },
+    )
+    .await;
+    batch_response(&item_ids, result)
+}
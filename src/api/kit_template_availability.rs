@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{
+    dsl::not, BelongingToDsl as _, BoolExpressionMethods as _, ExpressionMethods as _,
+    GroupedBy as _, QueryDsl as _, SelectableHelper as _,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{
+    kit_template_list::load_kit_template, ApiError, ApiResult, Application, AuthenticatedUser,
+    NoPermission,
+};
+use crate::{
+    models::{
+        item::{Item as ItemModel, ItemStatus},
+        tag::{ItemTag, Tag as TagModel},
+    },
+    schema::{items, kits, kits_items, reservations, reservations_items, tags},
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct AvailabilityQuery {
+    /// Start of the period the items are needed in UTC. Will default to now if unset.
+    from: Option<chrono::DateTime<Utc>>,
+    /// End of the period the items are needed in UTC. Will default to a day after `from`.
+    to: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct KitTemplateAvailability {
+    /// Whether every line of the template can be filled
+    available: bool,
+    /// Availability of each line of the template
+    tags: Vec<KitTemplateLineAvailability>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct KitTemplateLineAvailability {
+    /// Tag of the required items
+    tag_id: i64,
+    /// Number of items required with this tag
+    quantity: i32,
+    /// Ids of the suggested items, fewer than required if the line cannot be filled
+    suggested: Vec<i64>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Path(kit_template_id): Path<i64>,
+    Query(AvailabilityQuery { from, to }): Query<AvailabilityQuery>,
+) -> ApiResult<Json<KitTemplateAvailability>> {
+    let from = from.unwrap_or_else(|| Utc::now());
+    let to = to.unwrap_or(from + TimeDelta::days(1));
+    if to <= from {
+        return Err(ApiError::InvalidPeriod(from, to));
+    }
+    let mut conn = state.database.get().await?;
+    let (_, template_tags) = load_kit_template(&mut conn, kit_template_id).await?;
+
+    // items already lent together stay in their kit
+    let kit_items = kits_items::table
+        .inner_join(kits::table)
+        .filter(kits::closed_ts.is_null())
+        .select(kits_items::item_id);
+    // items reserved during the period are kept for their holder
+    let reserved_items = reservations_items::table
+        .inner_join(reservations::table)
+        .filter(reservations::starts_ts.lt(to.naive_utc()))
+        .filter(reservations::ends_ts.gt(from.naive_utc()))
+        .select(reservations_items::item_id);
+    // lent items can be suggested if they are due back before the period
+    let items = items::table
+        .filter(
+            items::status.eq(ItemStatus::InService).or(items::status
+                .eq(ItemStatus::Borrowed)
+                .and(items::due_back.le(from.naive_utc()))),
+        )
+        .filter(items::archived_ts.is_null())
+        .filter(not(items::id.eq_any(kit_items)))
+        .filter(not(items::id.eq_any(reserved_items)))
+        .order_by(items::id.asc())
+        .get_results::<ItemModel>(&mut conn)
+        .await?;
+    let item_tags = ItemTag::belonging_to(&items)
+        .inner_join(tags::table)
+        .select((ItemTag::as_select(), TagModel::as_select()))
+        .get_results::<(ItemTag, TagModel)>(&mut conn)
+        .await?
+        .grouped_by(&items);
+
+    // items which can be used until the end of the period, the ones furthest from
+    // their next inspection first
+    let mut candidates = items
+        .into_iter()
+        .zip(item_tags)
+        .filter_map(|(item, item_tags)| {
+            let tags = item_tags.iter().map(|(_, tag)| tag);
+            let next_inspection_due = item.next_inspection_due(tags.clone());
+            let end_of_life = item.end_of_life(tags);
+            (next_inspection_due.map_or(true, |due| due > to)
+                && end_of_life.map_or(true, |end_of_life| end_of_life > to))
+            .then(|| {
+                let tag_ids = item_tags
+                    .iter()
+                    .map(|(item_tag, _)| item_tag.tag_id)
+                    .collect::<HashSet<_>>();
+                (item.id, next_inspection_due, tag_ids)
+            })
+        })
+        .collect::<Vec<_>>();
+    // items without inspections due first
+    candidates.sort_by_key(|(_, next_inspection_due, _)| {
+        (
+            next_inspection_due.is_some(),
+            std::cmp::Reverse(*next_inspection_due),
+        )
+    });
+
+    let mut suggested_items = HashSet::new();
+    let lines = template_tags
+        .into_iter()
+        .map(|template_tag| {
+            let suggested = candidates
+                .iter()
+                .filter(|(item_id, _, tag_ids)| {
+                    tag_ids.contains(&template_tag.tag_id) && !suggested_items.contains(item_id)
+                })
+                .map(|(item_id, _, _)| *item_id)
+                .take(usize::try_from(template_tag.quantity).unwrap_or(0))
+                .collect::<Vec<_>>();
+            suggested_items.extend(suggested.iter().copied());
+            KitTemplateLineAvailability {
+                tag_id: template_tag.tag_id,
+                quantity: template_tag.quantity,
+                suggested,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(KitTemplateAvailability {
+        available: lines
+            .iter()
+            .all(|line| line.suggested.len() as i64 >= i64::from(line.quantity)),
+        tags: lines,
+    }))
+}
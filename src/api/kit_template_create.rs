@@ -0,0 +1,58 @@
+use axum::{extract::State, Json};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use crate::{
+    models::kit_template::{
+        InsertKitTemplate, InsertKitTemplateTag, KitTemplate as KitTemplateModel,
+    },
+    schema::{kit_templates, kit_templates_tags},
+};
+
+use super::{
+    kit_template_list::{load_kit_template, KitTemplate, KitTemplateLine},
+    ApiResult, Application, AuthenticatedUser, ManageItems,
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct CreateKitTemplate {
+    /// Name of the template to create
+    name: String,
+    /// Items required by the template
+    tags: Vec<KitTemplateLine>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Json(CreateKitTemplate { name, tags }): Json<CreateKitTemplate>,
+) -> ApiResult<Json<KitTemplate>> {
+    let mut conn = state.database.get().await?;
+    let kit_template = conn
+        .transaction(|conn| {
+            async move {
+                let kit_template = diesel::insert_into(kit_templates::table)
+                    .values(InsertKitTemplate { name })
+                    .returning(kit_templates::all_columns)
+                    .get_result::<KitTemplateModel>(conn)
+                    .await?;
+                diesel::insert_into(kit_templates_tags::table)
+                    .values(
+                        tags.into_iter()
+                            .map(|line| InsertKitTemplateTag {
+                                kit_template_id: kit_template.id,
+                                tag_id: line.tag_id,
+                                quantity: line.quantity,
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .execute(conn)
+                    .await?;
+                load_kit_template(conn, kit_template.id).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(kit_template.into()))
+}
@@ -0,0 +1,21 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, ManageItems};
+use crate::schema::*;
+
+pub async fn handler(
+    _auth: AuthenticatedUser<ManageItems>,
+    state: State<Application>,
+    Path(kit_template_id): Path<i64>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    Ok(diesel::delete(kit_templates::table.find(kit_template_id))
+        .execute(&mut conn)
+        .await
+        .map(|_| Json(()))?)
+}
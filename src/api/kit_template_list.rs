@@ -0,0 +1,85 @@
+use axum::{extract::State, Json};
+use diesel::{BelongingToDsl as _, ExpressionMethods as _, GroupedBy as _, QueryDsl as _};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{
+    models::kit_template::{KitTemplate as KitTemplateModel, KitTemplateTag},
+    schema::{kit_templates, kit_templates_tags},
+};
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct KitTemplate {
+    /// Id of the template
+    id: i64,
+    /// Name of the template
+    name: String,
+    /// Items required by the template
+    tags: Vec<KitTemplateLine>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct KitTemplateLine {
+    /// Tag of the required items
+    pub(crate) tag_id: i64,
+    /// Number of items required with this tag
+    pub(crate) quantity: i32,
+}
+
+impl From<(KitTemplateModel, Vec<KitTemplateTag>)> for KitTemplate {
+    fn from(value: (KitTemplateModel, Vec<KitTemplateTag>)) -> Self {
+        Self {
+            id: value.0.id,
+            name: value.0.name,
+            tags: value
+                .1
+                .into_iter()
+                .map(|template_tag| KitTemplateLine {
+                    tag_id: template_tag.tag_id,
+                    quantity: template_tag.quantity,
+                })
+                .collect(),
+        }
+    }
+}
+
+pub(crate) async fn load_kit_template(
+    conn: &mut diesel_async::AsyncPgConnection,
+    kit_template_id: i64,
+) -> ApiResult<(KitTemplateModel, Vec<KitTemplateTag>)> {
+    let kit_template = kit_templates::table
+        .find(kit_template_id)
+        .get_result::<KitTemplateModel>(conn)
+        .await?;
+    let template_tags = KitTemplateTag::belonging_to(&kit_template)
+        .order_by(kit_templates_tags::id.asc())
+        .get_results::<KitTemplateTag>(conn)
+        .await?;
+    Ok((kit_template, template_tags))
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+) -> ApiResult<Json<Vec<KitTemplate>>> {
+    let mut conn = state.database.get().await?;
+    let kit_templates = kit_templates::table
+        .order_by(kit_templates::name.asc())
+        .get_results::<KitTemplateModel>(&mut conn)
+        .await?;
+    let template_tags = KitTemplateTag::belonging_to(&kit_templates)
+        .order_by(kit_templates_tags::id.asc())
+        .get_results::<KitTemplateTag>(&mut conn)
+        .await?
+        .grouped_by(&kit_templates);
+
+    Ok(Json(
+        kit_templates
+            .into_iter()
+            .zip(template_tags)
+            .map(|(kit_template, template_tags)| (kit_template, template_tags).into())
+            .collect::<Vec<KitTemplate>>(),
+    ))
+}
@@ -0,0 +1,28 @@
+use axum::Json;
+
+use super::{ApiResult, AuthenticatedUser, NoPermission};
+use crate::models::{event::EventKind, item::ItemStatus, lifecycle::Lifecycle};
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct LifecycleState {
+    /// Status of an item
+    status: ItemStatus,
+    /// Events allowed for an item with this status
+    events: Vec<EventKind>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+) -> ApiResult<Json<Vec<LifecycleState>>> {
+    let lifecycle = Lifecycle::current();
+    Ok(Json(
+        ItemStatus::ALL
+            .into_iter()
+            .map(|status| LifecycleState {
+                status,
+                events: lifecycle.allowed(status).to_vec(),
+            })
+            .collect(),
+    ))
+}
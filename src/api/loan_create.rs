@@ -0,0 +1,108 @@
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use crate::{
+    models::{
+        event::{BatchError, Event, EventData},
+        member::Member,
+    },
+    schema::members,
+};
+
+use super::{ApiResult, Application, AuthenticatedUser, LendItems};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct CreateLoan {
+    /// Ids of the borrowed items
+    item_ids: Vec<i64>,
+    /// Member who borrows the items
+    borrower_id: i64,
+    /// Time the items are expected back in UTC
+    due_back: Option<chrono::DateTime<Utc>>,
+    /// Time of the borrow in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct ItemOutcome {
+    /// Id of the item
+    item_id: i64,
+    /// Id of the recorded event, unset if the whole batch was rejected
+    event_id: Option<i64>,
+    /// Why the item refused the event
+    error: Option<String>,
+}
+
+/// Report the outcome of a batch of events for every item
+pub(crate) fn batch_response(
+    item_ids: &[i64],
+    result: Result<Vec<Event>, BatchError>,
+) -> ApiResult<(StatusCode, Json<Vec<ItemOutcome>>)> {
+    match result {
+        Ok(events) => Ok((
+            StatusCode::OK,
+            Json(
+                events
+                    .into_iter()
+                    .map(|event| ItemOutcome {
+                        item_id: event.item_id,
+                        event_id: Some(event.id),
+                        error: None,
+                    })
+                    .collect(),
+            ),
+        )),
+        Err(BatchError::Rejected(rejected)) => Ok((
+            StatusCode::BAD_REQUEST,
+            Json(
+                item_ids
+                    .iter()
+                    .map(|item_id| ItemOutcome {
+                        item_id: *item_id,
+                        event_id: None,
+                        error: rejected
+                            .iter()
+                            .find(|(rejected_id, _)| rejected_id == item_id)
+                            .map(|(_, error)| error.to_string()),
+                    })
+                    .collect(),
+            ),
+        )),
+        Err(BatchError::Api(error)) => Err(error),
+    }
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Json(CreateLoan {
+        item_ids,
+        borrower_id,
+        due_back,
+        ts,
+    }): Json<CreateLoan>,
+) -> ApiResult<(StatusCode, Json<Vec<ItemOutcome>>)> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    let borrower = members::table
+        .find(borrower_id)
+        .get_result::<Member>(&mut conn)
+        .await?;
+    let result = Event::insert_events(
+        &mut conn,
+        &item_ids,
+        ts,
+        EventData::Borrowed {
+            borrower_id: borrower.id,
+            borrower: borrower.name,
+            validator: auth.claims.login,
+            due_back,
+        },
+    )
+    .await;
+    batch_response(&item_ids, result)
+}
@@ -0,0 +1,86 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{ExpressionMethods as _, PgSortExpressionMethods as _, QueryDsl as _};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{
+    models::item::{Item as ItemModel, ItemStatus},
+    schema::items,
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct LoansQuery {
+    /// Only return loans past their due date
+    overdue: Option<bool>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct Loan {
+    /// Id of the borrowed item
+    item_id: i64,
+    /// Name of the borrowed item
+    name: String,
+    /// Optional serial number
+    serial_number: Option<String>,
+    /// Member who borrows the item
+    borrower_id: Option<i64>,
+    /// Name of the member at the time of the borrow
+    borrower: String,
+    /// Time of the borrow
+    borrowed_ts: Option<chrono::DateTime<Utc>>,
+    /// Time the item is expected back
+    due_back: Option<chrono::DateTime<Utc>>,
+    /// Days since the item should have been returned, if it is overdue
+    days_overdue: Option<i64>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Query(LoansQuery { overdue }): Query<LoansQuery>,
+) -> ApiResult<Json<Vec<Loan>>> {
+    let now = Utc::now();
+    let mut conn = state.database.get().await?;
+    let mut query = items::table
+        .filter(items::status.eq(ItemStatus::Borrowed))
+        .into_boxed();
+    if overdue.unwrap_or(false) {
+        query = query.filter(items::due_back.lt(now.naive_utc()));
+    }
+    let items = query
+        // most urgent first
+        .order_by((items::due_back.asc().nulls_last(), items::borrowed_ts.asc()))
+        .get_results::<ItemModel>(&mut conn)
+        .await?;
+
+    Ok(Json(
+        items
+            .into_iter()
+            .map(|item| {
+                let due_back = item
+                    .due_back
+                    .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc));
+                Loan {
+                    item_id: item.id,
+                    name: item.name,
+                    serial_number: item.serial_number,
+                    borrower_id: item.borrower_id,
+                    borrower: item.borrower.unwrap_or_default(),
+                    borrowed_ts: item
+                        .borrowed_ts
+                        .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
+                    due_back,
+                    days_overdue: due_back
+                        .filter(|due_back| *due_back < now)
+                        .map(|due_back| (now - due_back).num_days()),
+                }
+            })
+            .collect(),
+    ))
+}
@@ -0,0 +1,37 @@
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+
+use crate::models::event::{Event, EventData};
+
+use super::{
+    loan_create::{batch_response, ItemOutcome},
+    ApiResult, Application, AuthenticatedUser, LendItems,
+};
+
+#[derive(ts_rs::TS, serde::Deserialize)]
+#[ts(export)]
+pub struct ReturnLoan {
+    /// Ids of the returned items
+    item_ids: Vec<i64>,
+    /// Time of the return in UTC. Will default to now if unset.
+    ts: Option<chrono::DateTime<Utc>>,
+}
+
+pub async fn handler(
+    auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Json(ReturnLoan { item_ids, ts }): Json<ReturnLoan>,
+) -> ApiResult<(StatusCode, Json<Vec<ItemOutcome>>)> {
+    let mut conn = state.database.get().await?;
+    let ts = ts.unwrap_or_else(|| Utc::now());
+    let result = Event::insert_events(
+        &mut conn,
+        &item_ids,
+        ts,
+        EventData::Returned {
+            validator: auth.claims.login,
+        },
+    )
+    .await;
+    batch_response(&item_ids, result)
+}
@@ -0,0 +1,45 @@
+use axum::{extract::State, Json};
+use diesel_async::RunQueryDsl as _;
+
+use crate::{
+    models::member::{InsertMember, Member as MemberModel},
+    schema::members,
+};
+
+use super::{member_list::Member, ApiResult, Application, AuthenticatedUser, LendItems};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct CreateMember {
+    /// Display name of the member
+    name: String,
+    /// Optional contact email
+    email: Option<String>,
+    /// Optional contact phone number
+    phone: Option<String>,
+}
+
+impl From<CreateMember> for InsertMember {
+    fn from(value: CreateMember) -> Self {
+        Self {
+            name: value.name,
+            email: value.email,
+            phone: value.phone,
+        }
+    }
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Json(data): Json<CreateMember>,
+) -> ApiResult<Json<Member>> {
+    let mut conn = state.database.get().await?;
+    let member = diesel::insert_into(members::table)
+        .values(InsertMember::from(data))
+        .returning(members::all_columns)
+        .get_result::<MemberModel>(&mut conn)
+        .await?;
+
+    Ok(Json(member.into()))
+}
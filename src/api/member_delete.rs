@@ -0,0 +1,47 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::{ExpressionMethods as _, QueryDsl as _};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use super::{
+    member_loans::borrowed_by, ApiError, ApiResult, Application, AuthenticatedUser, LendItems,
+};
+use crate::schema::*;
+
+pub async fn handler(
+    _auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(member_id): Path<i64>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    conn.transaction(|conn| {
+        async {
+            // members appearing in the history must be merged instead
+            let events = events::table
+                .filter(borrowed_by(member_id))
+                .count()
+                .get_result::<i64>(conn)
+                .await?;
+            if events > 0 {
+                return Err(ApiError::MemberInUse(member_id));
+            }
+            // reservations would silently disappear with their holder
+            let reservations = reservations::table
+                .filter(reservations::member_id.eq(member_id))
+                .count()
+                .get_result::<i64>(conn)
+                .await?;
+            if reservations > 0 {
+                return Err(ApiError::MemberHasReservations(member_id));
+            }
+            diesel::delete(members::table.find(member_id))
+                .execute(conn)
+                .await?;
+            Ok(Json(()))
+        }
+        .scope_boxed()
+    })
+    .await
+}
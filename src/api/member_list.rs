@@ -0,0 +1,48 @@
+use axum::{extract::State, Json};
+use diesel::{ExpressionMethods as _, QueryDsl as _};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{models::member::Member as MemberModel, schema::members};
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct Member {
+    /// Id of the member
+    id: i64,
+    /// Display name of the member
+    name: String,
+    /// Optional contact email
+    email: Option<String>,
+    /// Optional contact phone number
+    phone: Option<String>,
+}
+
+impl From<MemberModel> for Member {
+    fn from(value: MemberModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            email: value.email,
+            phone: value.phone,
+        }
+    }
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+) -> ApiResult<Json<Vec<Member>>> {
+    let mut conn = state.database.get().await?;
+    let members = members::table
+        .order_by(members::name.asc())
+        .get_results::<MemberModel>(&mut conn)
+        .await?;
+
+    Ok(Json(
+        members
+            .into_iter()
+            .map(|member_model| member_model.into())
+            .collect::<Vec<Member>>(),
+    ))
+}
@@ -0,0 +1,130 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{
+    dsl::sql,
+    pg::Pg,
+    sql_types::{BigInt, Bool},
+    BelongingToDsl as _, BoxableExpression, ExpressionMethods as _, GroupedBy as _, QueryDsl as _,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{
+    models::{
+        event::{Event, EventData, EventKind},
+        item::{Item as ItemModel, ItemStatus},
+    },
+    schema::{events, items},
+};
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct PastLoan {
+    /// Id of the borrowed item
+    item_id: i64,
+    /// Name of the borrowed item
+    name: String,
+    /// Optional serial number
+    serial_number: Option<String>,
+    /// Time of the borrow
+    borrowed_ts: chrono::DateTime<Utc>,
+    /// Time the item was expected back
+    due_back: Option<chrono::DateTime<Utc>>,
+    /// Time the loan ended, unset while the item is still borrowed
+    ended_ts: Option<chrono::DateTime<Utc>>,
+    /// Event which ended the loan (usually a return)
+    ended_by: Option<EventKind>,
+    /// Days the item was, or still is, kept past its due date
+    days_late: Option<i64>,
+}
+
+impl PastLoan {
+    fn end(&mut self, ts: chrono::DateTime<Utc>, kind: Option<EventKind>) {
+        self.ended_ts = kind.map(|_| ts);
+        self.ended_by = kind;
+        self.days_late = self
+            .due_back
+            .filter(|due_back| *due_back < ts)
+            .map(|due_back| (ts - due_back).num_days());
+    }
+}
+
+/// Events borrowing an item for a member, or amended into such a borrow
+pub(crate) fn borrowed_by(
+    member_id: i64,
+) -> Box<dyn BoxableExpression<events::table, Pg, SqlType = Bool>> {
+    Box::new(
+        sql::<Bool>("((events.data->>'borrower_id')::bigint = ")
+            .bind::<BigInt, _>(member_id)
+            .sql(" OR (events.data->'data'->>'borrower_id')::bigint = ")
+            .bind::<BigInt, _>(member_id)
+            .sql(")"),
+    )
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Path(member_id): Path<i64>,
+) -> ApiResult<Json<Vec<PastLoan>>> {
+    let now = Utc::now();
+    let mut conn = state.database.get().await?;
+    // items borrowed at least once by this member
+    let borrowed_items = events::table
+        .filter(borrowed_by(member_id))
+        .select(events::item_id);
+    let items = items::table
+        .filter(items::id.eq_any(borrowed_items))
+        .get_results::<ItemModel>(&mut conn)
+        .await?;
+    let events = Event::belonging_to(&items)
+        .order_by((events::ts.asc(), events::id.asc()))
+        .get_results::<Event>(&mut conn)
+        .await?
+        .grouped_by(&items);
+
+    let mut loans = vec![];
+    for (item, item_events) in items.iter().zip(events) {
+        let mut current: Option<PastLoan> = None;
+        for (event, data) in Event::effective_history(&item_events) {
+            let ts = chrono::DateTime::from_naive_utc_and_offset(event.ts, Utc);
+            match data {
+                EventData::Borrowed {
+                    borrower_id,
+                    due_back,
+                    ..
+                } if *borrower_id == member_id => {
+                    current = Some(PastLoan {
+                        item_id: item.id,
+                        name: item.name.clone(),
+                        serial_number: item.serial_number.clone(),
+                        borrowed_ts: ts,
+                        due_back: *due_back,
+                        ended_ts: None,
+                        ended_by: None,
+                        days_late: None,
+                    });
+                }
+                // the loan ends as soon as the item leaves the borrowed status
+                _ if data.resulting_status(ItemStatus::Borrowed) != ItemStatus::Borrowed => {
+                    if let Some(mut loan) = current.take() {
+                        loan.end(ts, Some(data.kind()));
+                        loans.push(loan);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(mut loan) = current {
+            loan.end(now, None);
+            loans.push(loan);
+        }
+    }
+    // most recent first
+    loans.sort_by(|a, b| b.borrowed_ts.cmp(&a.borrowed_ts));
+
+    Ok(Json(loans))
+}
@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::{
+    sql_types::{Array, BigInt},
+    ExpressionMethods as _, QueryDsl as _,
+};
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use crate::{models::member::Member as MemberModel, schema::*};
+
+use super::{member_list::Member, Administrate, ApiResult, Application, AuthenticatedUser};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct MergeMembers {
+    /// Ids of the duplicate members, removed once their history is moved
+    duplicate_ids: Vec<i64>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<Administrate>,
+    state: State<Application>,
+    Path(member_id): Path<i64>,
+    Json(MergeMembers { duplicate_ids }): Json<MergeMembers>,
+) -> ApiResult<Json<Member>> {
+    let duplicate_ids: Vec<i64> = duplicate_ids
+        .into_iter()
+        .filter(|id| *id != member_id)
+        .collect();
+    let mut conn = state.database.get().await?;
+    conn.transaction(|conn| {
+        async {
+            let member = members::table
+                .find(member_id)
+                .get_result::<MemberModel>(conn)
+                .await?;
+
+            // borrow events keep their name snapshot, only the reference moves
+            diesel::sql_query(
+                "UPDATE events \
+                SET data = jsonb_set(data, '{borrower_id}', to_jsonb($1)) \
+                WHERE data->>'kind' = 'Borrowed' AND (data->>'borrower_id')::bigint = ANY($2)",
+            )
+            .bind::<BigInt, _>(member_id)
+            .bind::<Array<BigInt>, _>(&duplicate_ids)
+            .execute(conn)
+            .await?;
+            diesel::sql_query(
+                "UPDATE events \
+                SET data = jsonb_set(data, '{data,borrower_id}', to_jsonb($1)) \
+                WHERE data->>'kind' = 'Amended' \
+                AND (data->'data'->>'borrower_id')::bigint = ANY($2)",
+            )
+            .bind::<BigInt, _>(member_id)
+            .bind::<Array<BigInt>, _>(&duplicate_ids)
+            .execute(conn)
+            .await?;
+            diesel::update(items::table.filter(items::borrower_id.eq_any(&duplicate_ids)))
+                .set(items::borrower_id.eq(member_id))
+                .execute(conn)
+                .await?;
+            diesel::update(
+                reservations::table.filter(reservations::member_id.eq_any(&duplicate_ids)),
+            )
+            .set(reservations::member_id.eq(member_id))
+            .execute(conn)
+            .await?;
+            diesel::delete(members::table.filter(members::id.eq_any(&duplicate_ids)))
+                .execute(conn)
+                .await?;
+
+            Ok(Json(member.into()))
+        }
+        .scope_boxed()
+    })
+    .await
+}
@@ -0,0 +1,33 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use crate::{
+    models::member::{InsertMember, Member as MemberModel},
+    schema::members,
+};
+
+use super::{
+    member_create::CreateMember, member_list::Member, ApiResult, Application, AuthenticatedUser,
+    LendItems,
+};
+
+/// Past events keep the name the member had at the time
+pub async fn handler(
+    _auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(member_id): Path<i64>,
+    Json(data): Json<CreateMember>,
+) -> ApiResult<Json<Member>> {
+    let mut conn = state.database.get().await?;
+    let member = diesel::update(members::table.find(member_id))
+        .set(InsertMember::from(data))
+        .returning(members::all_columns)
+        .get_result::<MemberModel>(&mut conn)
+        .await?;
+
+    Ok(Json(member.into()))
+}
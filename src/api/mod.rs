@@ -21,13 +21,62 @@ use tokio::task::JoinError;
 
 use crate::{
     db::DbPool,
-    models::{event::EventData, user::User},
+    models::{
+        event::{EventData, EventKind},
+        item::ItemStatus,
+        user::User,
+    },
 };
 
+pub mod event_amend;
+pub mod event_void;
+pub mod inspections_due;
+pub mod item_actions;
+pub mod item_archive;
+pub mod item_borrow;
+pub mod item_by_serial;
 pub mod item_create;
 pub mod item_details;
+pub mod item_duplicate_serials;
+pub mod item_found;
+pub mod item_historical_event;
 pub mod item_inspect;
 pub mod item_list;
+pub mod item_lose;
+pub mod item_maintain;
+pub mod item_purge;
+pub mod item_quarantine;
+pub mod item_restore;
+pub mod item_retire;
+pub mod item_return;
+pub mod item_update;
+pub mod item_use;
+pub mod kit_add_item;
+pub mod kit_borrow;
+pub mod kit_close;
+pub mod kit_create;
+pub mod kit_inspect;
+pub mod kit_list;
+pub mod kit_remove_item;
+pub mod kit_return;
+pub mod kit_template_availability;
+pub mod kit_template_create;
+pub mod kit_template_delete;
+pub mod kit_template_list;
+pub mod lifecycle;
+pub mod loan_create;
+pub mod loan_list;
+pub mod loan_return;
+pub mod member_create;
+pub mod member_delete;
+pub mod member_list;
+pub mod member_loans;
+pub mod member_merge;
+pub mod member_update;
+pub mod reservation_calendar;
+pub mod reservation_create;
+pub mod reservation_delete;
+pub mod search;
 pub mod r#static;
 pub mod tag_create;
 pub mod tag_delete;
@@ -49,13 +98,67 @@ pub enum ApiError {
     PasswordHash(String),
     #[error("Error joining task: {0}")]
     JoinError(#[from] JoinError),
-    #[error("Transitioning from event {0:?} to {1:?} is not allowed")]
-    InvalidTransition(Option<EventData>, EventData),
+    #[error("Event {1:?} is not allowed for an item with status {0:?}")]
+    InvalidTransition(ItemStatus, EventData),
     #[error("Cannot insert event with time {0} after an event with time {1}")]
     InvalidEventTime(
         chrono::prelude::DateTime<Utc>,
         chrono::prelude::DateTime<Utc>,
     ),
+    #[error("Fall factor {0} is not between 0 and 2")]
+    InvalidFallFactor(f32),
+    #[error("Item reached its end of life on {0}")]
+    EndOfLife(chrono::prelude::DateTime<Utc>),
+    #[error("Item cannot be due back on {0}, before it is borrowed")]
+    InvalidDueDate(chrono::prelude::DateTime<Utc>),
+    #[error("Member {0} appears in the history of items")]
+    MemberInUse(i64),
+    #[error("Member {0} holds reservations")]
+    MemberHasReservations(i64),
+    #[error("Item {0} does not exist")]
+    UnknownItem(i64),
+    #[error("Item {0} already belongs to the open kit {1}")]
+    ItemInOpenKit(i64, i64),
+    #[error("Kit {0} was taken apart")]
+    KitClosed(i64),
+    #[error("Item {0} is already reserved during this period (reservation {1})")]
+    ReservationConflict(i64, i64),
+    #[error("Item {0} is reserved by another member from {2} (reservation {1}), the loan must be due back before")]
+    ItemReserved(i64, i64, chrono::prelude::DateTime<Utc>),
+    #[error("Item {0} is lent to another member during this period")]
+    ItemLent(i64),
+    #[error("Reservation cannot end on {1}, before it starts on {0}")]
+    InvalidReservationPeriod(
+        chrono::prelude::DateTime<Utc>,
+        chrono::prelude::DateTime<Utc>,
+    ),
+    #[error("Item {0} is borrowed, it must be returned first")]
+    ItemBorrowed(i64),
+    #[error("Period cannot end on {1}, before it starts on {0}")]
+    InvalidPeriod(
+        chrono::prelude::DateTime<Utc>,
+        chrono::prelude::DateTime<Utc>,
+    ),
+    #[error("Item {0} is archived")]
+    ItemArchived(i64),
+    #[error("Item {0} has a history, only administrators can purge it")]
+    ItemHasHistory(i64),
+    #[error("Purge of item {0} was not confirmed with its name")]
+    PurgeNotConfirmed(i64),
+    #[error("Another {0} already has the serial number {1}")]
+    DuplicateSerial(String, String),
+    #[error("Tag {0} does not exist")]
+    UnknownTag(i64),
+    #[error("Inspection period of {0} days is negative")]
+    InvalidInspectionPeriod(i32),
+    #[error("Event {0} cannot be corrected")]
+    InvalidCorrection(i64),
+    #[error("{0:?} events cannot be inserted in the history of an item")]
+    NotLifecycleEvent(EventKind),
+    #[error("A {0:?} event cannot be amended into a {1:?} event")]
+    AmendedKindMismatch(EventKind, EventKind),
+    #[error("Invalid duration `{0}`, expected a number of days (30d) or weeks (4w)")]
+    InvalidDuration(String),
 }
 
 impl IntoResponse for ApiError {
@@ -72,6 +175,30 @@ impl IntoResponse for ApiError {
             ApiError::JoinError(_) => (StatusCode::INTERNAL_SERVER_ERROR, message),
             ApiError::InvalidTransition(..) => (StatusCode::BAD_REQUEST, message),
             ApiError::InvalidEventTime(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::InvalidFallFactor(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::EndOfLife(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::InvalidDueDate(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::MemberInUse(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::MemberHasReservations(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::UnknownItem(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ItemInOpenKit(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::KitClosed(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ReservationConflict(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ItemReserved(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ItemLent(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::InvalidReservationPeriod(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ItemBorrowed(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::InvalidPeriod(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ItemArchived(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ItemHasHistory(_) => (StatusCode::FORBIDDEN, message),
+            ApiError::PurgeNotConfirmed(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::DuplicateSerial(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::UnknownTag(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::InvalidInspectionPeriod(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::InvalidCorrection(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::NotLifecycleEvent(_) => (StatusCode::BAD_REQUEST, message),
+            ApiError::AmendedKindMismatch(..) => (StatusCode::BAD_REQUEST, message),
+            ApiError::InvalidDuration(_) => (StatusCode::BAD_REQUEST, message),
         }
         .into_response()
     }
@@ -93,6 +220,7 @@ pub struct ApiClaims {
     perm_items: bool,
     perm_action_inspect: bool,
     perm_action_lend: bool,
+    perm_action_maintain: bool,
 }
 
 impl From<User> for ApiClaims {
@@ -102,6 +230,7 @@ impl From<User> for ApiClaims {
             perm_users: value.perm_users,
             perm_action_lend: value.perm_action_lend,
             perm_action_inspect: value.perm_action_inspect,
+            perm_action_maintain: value.perm_action_maintain,
             perm_tags: value.perm_tags,
             perm_items: value.perm_items,
         }
@@ -210,5 +339,8 @@ permissions!(
     ManageUsers => perm_users,
     ManageTags => perm_tags,
     LendItems => perm_action_lend,
-    InspectItems => perm_action_inspect
+    InspectItems => perm_action_inspect,
+    MaintainItems => perm_action_maintain,
+    // user managers can grant themselves any permission, they are the administrators
+    Administrate => perm_users
 );
@@ -0,0 +1,151 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{
+    BelongingToDsl as _, ExpressionMethods as _, GroupedBy as _, QueryDsl as _,
+    SelectableHelper as _,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::{
+    models::{
+        item::{Item as ItemModel, ItemStatus},
+        reservation::{Reservation as ReservationModel, ReservationItem},
+    },
+    schema::{items, reservations, reservations_items},
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct CalendarQuery {
+    /// Start of the calendar in UTC. Will default to now if unset.
+    from: Option<chrono::DateTime<Utc>>,
+    /// End of the calendar in UTC. Will default to 30 days after its start if unset.
+    to: Option<chrono::DateTime<Utc>>,
+    /// Only return the calendar of this item
+    item_id: Option<i64>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct Calendar {
+    /// Reservations overlapping the calendar
+    reservations: Vec<Reservation>,
+    /// Items currently borrowed, unavailable until they are returned
+    loans: Vec<CalendarLoan>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct Reservation {
+    /// Id of the reservation
+    id: i64,
+    /// Member holding the reservation
+    member_id: i64,
+    /// Kit reserved as a whole, if any
+    kit_id: Option<i64>,
+    /// Start of the reserved period
+    starts_ts: chrono::DateTime<Utc>,
+    /// End of the reserved period, excluded
+    ends_ts: chrono::DateTime<Utc>,
+    /// Optional comment, ex: name of the trip
+    comment: Option<String>,
+    /// Ids of all reserved items
+    items: Vec<i64>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct CalendarLoan {
+    /// Id of the borrowed item
+    item_id: i64,
+    /// Member who borrows the item
+    borrower_id: Option<i64>,
+    /// Time of the borrow
+    borrowed_ts: Option<chrono::DateTime<Utc>>,
+    /// Time the item is expected back
+    due_back: Option<chrono::DateTime<Utc>>,
+}
+
+impl From<(ReservationModel, Vec<i64>)> for Reservation {
+    fn from(value: (ReservationModel, Vec<i64>)) -> Self {
+        Self {
+            id: value.0.id,
+            member_id: value.0.member_id,
+            kit_id: value.0.kit_id,
+            starts_ts: chrono::DateTime::from_naive_utc_and_offset(value.0.starts_ts, Utc),
+            ends_ts: chrono::DateTime::from_naive_utc_and_offset(value.0.ends_ts, Utc),
+            comment: value.0.comment,
+            items: value.1,
+        }
+    }
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Query(CalendarQuery { from, to, item_id }): Query<CalendarQuery>,
+) -> ApiResult<Json<Calendar>> {
+    let from = from.unwrap_or_else(|| Utc::now());
+    let to = to.unwrap_or(from + TimeDelta::days(30));
+    let mut conn = state.database.get().await?;
+
+    let mut query = reservations::table
+        .filter(reservations::starts_ts.lt(to.naive_utc()))
+        .filter(reservations::ends_ts.gt(from.naive_utc()))
+        .order_by(reservations::starts_ts.asc())
+        .into_boxed();
+    if let Some(item_id) = item_id {
+        query = query.filter(
+            reservations::id.eq_any(
+                reservations_items::table
+                    .filter(reservations_items::item_id.eq(item_id))
+                    .select(reservations_items::reservation_id),
+            ),
+        );
+    }
+    let reservations = query.get_results::<ReservationModel>(&mut conn).await?;
+    let reservation_items = ReservationItem::belonging_to(&reservations)
+        .select(ReservationItem::as_select())
+        .get_results::<ReservationItem>(&mut conn)
+        .await?
+        .grouped_by(&reservations);
+
+    let mut query = items::table
+        .filter(items::status.eq(ItemStatus::Borrowed))
+        .into_boxed();
+    if let Some(item_id) = item_id {
+        query = query.filter(items::id.eq(item_id));
+    }
+    let loans = query.get_results::<ItemModel>(&mut conn).await?;
+
+    Ok(Json(Calendar {
+        reservations: reservations
+            .into_iter()
+            .zip(reservation_items)
+            .map(|(reservation, reservation_items)| {
+                let item_ids = reservation_items
+                    .into_iter()
+                    .map(|reservation_item| reservation_item.item_id)
+                    .collect();
+                (reservation, item_ids).into()
+            })
+            .collect(),
+        loans: loans
+            .into_iter()
+            .map(|item| CalendarLoan {
+                item_id: item.id,
+                borrower_id: item.borrower_id,
+                borrowed_ts: item
+                    .borrowed_ts
+                    .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
+                due_back: item
+                    .due_back
+                    .map(|ts| chrono::DateTime::from_naive_utc_and_offset(ts, Utc)),
+            })
+            .collect(),
+    }))
+}
@@ -0,0 +1,126 @@
+use axum::{extract::State, Json};
+use chrono::Utc;
+use diesel::QueryDsl as _;
+use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
+
+use crate::{
+    models::{
+        kit::Kit,
+        member::Member,
+        reservation::{InsertReservation, InsertReservationItem, Reservation as ReservationModel},
+    },
+    schema::{items, members, reservations, reservations_items},
+};
+
+use super::{
+    reservation_calendar::Reservation, ApiError, ApiResult, Application, AuthenticatedUser,
+    LendItems,
+};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(tag = "kind")]
+pub enum ReservationTarget {
+    /// Reserve a single item
+    Item {
+        /// Id of the reserved item
+        item_id: i64,
+    },
+    /// Reserve all the items of a kit
+    Kit {
+        /// Id of the reserved kit
+        kit_id: i64,
+    },
+}
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct CreateReservation {
+    /// Member holding the reservation
+    member_id: i64,
+    /// What is reserved
+    target: ReservationTarget,
+    /// Start of the reserved period in UTC
+    starts_ts: chrono::DateTime<Utc>,
+    /// End of the reserved period in UTC, excluded
+    ends_ts: chrono::DateTime<Utc>,
+    /// Optional comment, ex: name of the trip
+    comment: Option<String>,
+}
+
+pub async fn handler(
+    _auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Json(CreateReservation {
+        member_id,
+        target,
+        starts_ts,
+        ends_ts,
+        comment,
+    }): Json<CreateReservation>,
+) -> ApiResult<Json<Reservation>> {
+    if ends_ts <= starts_ts {
+        return Err(ApiError::InvalidReservationPeriod(starts_ts, ends_ts));
+    }
+    let mut conn = state.database.get().await?;
+    let reservation = conn
+        .transaction(|conn| {
+            async move {
+                let member = members::table
+                    .find(member_id)
+                    .get_result::<Member>(conn)
+                    .await?;
+                let (kit_id, item_ids) = match target {
+                    ReservationTarget::Item { item_id } => {
+                        let item_id = items::table
+                            .find(item_id)
+                            .select(items::id)
+                            .get_result::<i64>(conn)
+                            .await?;
+                        (None, vec![item_id])
+                    }
+                    ReservationTarget::Kit { kit_id } => {
+                        (Some(kit_id), Kit::open_item_ids(conn, kit_id).await?)
+                    }
+                };
+                ReservationModel::check_free_items(
+                    conn,
+                    &item_ids,
+                    member.id,
+                    starts_ts.naive_utc(),
+                    ends_ts.naive_utc(),
+                )
+                .await?;
+
+                let reservation = diesel::insert_into(reservations::table)
+                    .values(InsertReservation {
+                        member_id: member.id,
+                        kit_id,
+                        starts_ts: starts_ts.naive_utc(),
+                        ends_ts: ends_ts.naive_utc(),
+                        comment,
+                    })
+                    .returning(reservations::all_columns)
+                    .get_result::<ReservationModel>(conn)
+                    .await?;
+                diesel::insert_into(reservations_items::table)
+                    .values(
+                        item_ids
+                            .iter()
+                            .map(|item_id| InsertReservationItem {
+                                reservation_id: reservation.id,
+                                item_id: *item_id,
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .execute(conn)
+                    .await?;
+
+                Ok::<_, ApiError>((reservation, item_ids))
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(reservation.into()))
+}
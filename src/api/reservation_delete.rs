@@ -0,0 +1,21 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use diesel::QueryDsl as _;
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, LendItems};
+use crate::schema::*;
+
+pub async fn handler(
+    _auth: AuthenticatedUser<LendItems>,
+    state: State<Application>,
+    Path(reservation_id): Path<i64>,
+) -> ApiResult<Json<()>> {
+    let mut conn = state.database.get().await?;
+    Ok(diesel::delete(reservations::table.find(reservation_id))
+        .execute(&mut conn)
+        .await
+        .map(|_| Json(()))?)
+}
@@ -0,0 +1,159 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::Utc;
+use diesel::{
+    sql_types::{BigInt, Float4, Jsonb, Nullable, Text, Timestamptz, Varchar},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl as _;
+
+use super::{ApiResult, Application, AuthenticatedUser, NoPermission};
+use crate::models::{event::EventData, item::ItemStatus};
+
+#[derive(serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct SearchQuery {
+    /// Words to search, quotes and `-` are supported like in web search engines
+    q: String,
+    /// Maximum number of items and of events to return, defaults to 20
+    limit: Option<i64>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct SearchResults {
+    /// Items whose name or serial number match, best match first
+    items: Vec<ItemHit>,
+    /// Inspections whose comment match, best match first
+    events: Vec<EventHit>,
+}
+
+#[derive(serde::Serialize, ts_rs::TS, QueryableByName)]
+#[ts(export)]
+pub struct ItemHit {
+    /// Id of the item
+    #[diesel(sql_type = BigInt)]
+    id: i64,
+    /// Name of the item
+    #[diesel(sql_type = Varchar)]
+    name: String,
+    /// Optional serial number
+    #[diesel(sql_type = Nullable<Varchar>)]
+    serial_number: Option<String>,
+    /// Current status of the item
+    #[diesel(sql_type = Text)]
+    status: ItemStatus,
+    /// Relevance of the match
+    #[diesel(sql_type = Float4)]
+    rank: f32,
+    /// Name and serial number, with matching words in bold
+    #[diesel(sql_type = Text)]
+    snippet: String,
+}
+
+#[derive(serde::Serialize, ts_rs::TS, QueryableByName)]
+#[ts(export)]
+pub struct EventHit {
+    /// Id of the event
+    #[diesel(sql_type = BigInt)]
+    id: i64,
+    /// Id of the item of the event
+    #[diesel(sql_type = BigInt)]
+    item_id: i64,
+    /// Name of the item of the event
+    #[diesel(sql_type = Varchar)]
+    item_name: String,
+    /// Timestamp of the event
+    #[diesel(sql_type = Timestamptz)]
+    ts: chrono::DateTime<Utc>,
+    /// Details of the event, as amended
+    #[diesel(sql_type = Jsonb)]
+    data: EventData,
+    /// Relevance of the match
+    #[diesel(sql_type = Float4)]
+    rank: f32,
+    /// Excerpt of the comment, with matching words in bold
+    #[diesel(sql_type = Text)]
+    snippet: String,
+}
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+pub async fn handler(
+    _auth: AuthenticatedUser<NoPermission>,
+    state: State<Application>,
+    Query(SearchQuery { q, limit }): Query<SearchQuery>,
+) -> ApiResult<Json<SearchResults>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let mut conn = state.database.get().await?;
+
+    // the searched documents must be the expressions of the search indexes,
+    // and are escaped before being highlighted as the snippets are HTML
+    let items = diesel::sql_query(
+        "SELECT items.id, items.name, items.serial_number, items.status, \
+            ts_rank(document, query) AS rank, \
+            ts_headline('simple', \
+                replace(replace(replace( \
+                    items.name || ' ' || COALESCE(items.serial_number, ''), \
+                    '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), \
+                query) AS snippet \
+        FROM items, \
+            to_tsvector('simple', items.name || ' ' || COALESCE(items.serial_number, '')) \
+                AS document, \
+            websearch_to_tsquery('simple', $1) AS query \
+        WHERE to_tsvector('simple', items.name || ' ' || COALESCE(items.serial_number, '')) \
+                @@ query \
+            AND items.archived_ts IS NULL \
+        ORDER BY rank DESC, items.id \
+        LIMIT $2",
+    )
+    .bind::<Text, _>(&q)
+    .bind::<BigInt, _>(limit)
+    .get_results::<ItemHit>(&mut conn)
+    .await?;
+
+    // only the effective version of inspections is searched: voided ones are skipped,
+    // and amended ones are found through their latest amendment
+    let events = diesel::sql_query(
+        "SELECT target.id, target.item_id, items.name AS item_name, \
+            target.ts AT TIME ZONE 'UTC' AS ts, \
+            COALESCE(events.data->'data', events.data) AS data, \
+            ts_rank(document, query) AS rank, \
+            ts_headline('simple', \
+                replace(replace(replace( \
+                    COALESCE(events.data->>'comment', events.data->'data'->>'comment', ''), \
+                    '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), \
+                query) AS snippet \
+        FROM events \
+            INNER JOIN events AS target \
+                ON target.id = COALESCE((events.data->>'target_event_id')::bigint, events.id) \
+            INNER JOIN items ON items.id = events.item_id, \
+            to_tsvector('simple', \
+                COALESCE(events.data->>'comment', events.data->'data'->>'comment', '')) \
+                AS document, \
+            websearch_to_tsquery('simple', $1) AS query \
+        WHERE to_tsvector('simple', \
+                COALESCE(events.data->>'comment', events.data->'data'->>'comment', '')) \
+                @@ query \
+            AND events.data->>'kind' IN ('Inspected', 'Amended') \
+            AND target.data->>'kind' = 'Inspected' \
+            AND items.archived_ts IS NULL \
+            AND NOT EXISTS ( \
+                SELECT 1 FROM events AS correction \
+                WHERE correction.item_id = target.item_id \
+                    AND (correction.data->>'target_event_id')::bigint = target.id \
+                    AND (correction.data->>'kind' = 'Voided' \
+                        OR (correction.ts, correction.id) > (events.ts, events.id))) \
+        ORDER BY rank DESC, target.id \
+        LIMIT $2",
+    )
+    .bind::<Text, _>(&q)
+    .bind::<BigInt, _>(limit)
+    .get_results::<EventHit>(&mut conn)
+    .await?;
+
+    Ok(Json(SearchResults { items, events }))
+}
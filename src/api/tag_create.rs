@@ -13,6 +13,14 @@ use super::{tag_list::Tag, ApiResult, Application, AuthenticatedUser, ManageTags
 pub struct CreateTag {
     /// Name of the tag to create
     name: String,
+    /// Optional maximum lifetime from manufacture of tagged items
+    max_lifetime_years: Option<i32>,
+    /// Optional maximum lifetime from put into service of tagged items
+    max_service_years: Option<i32>,
+    /// Optional number of falls after which tagged items must be inspected
+    inspection_after_falls: Option<i32>,
+    /// Optional fall factor after which tagged items must be inspected
+    inspection_after_fall_factor: Option<f32>,
 }
 
 pub async fn handler(
@@ -22,7 +30,13 @@ pub async fn handler(
 ) -> ApiResult<Json<Tag>> {
     let mut conn = state.database.get().await?;
     let tag = diesel::insert_into(tags::table)
-        .values(InsertTagModel { name: data.name })
+        .values(InsertTagModel {
+            name: data.name,
+            max_lifetime_years: data.max_lifetime_years,
+            max_service_years: data.max_service_years,
+            inspection_after_falls: data.inspection_after_falls,
+            inspection_after_fall_factor: data.inspection_after_fall_factor,
+        })
         .returning(tags::all_columns)
         .get_result::<TagModel>(&mut conn)
         .await?;
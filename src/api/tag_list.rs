@@ -11,6 +11,14 @@ pub struct Tag {
     id: i64,
     /// Name of the tag
     name: String,
+    /// Optional maximum lifetime from manufacture of tagged items
+    max_lifetime_years: Option<i32>,
+    /// Optional maximum lifetime from put into service of tagged items
+    max_service_years: Option<i32>,
+    /// Optional number of falls after which tagged items must be inspected
+    inspection_after_falls: Option<i32>,
+    /// Optional fall factor after which tagged items must be inspected
+    inspection_after_fall_factor: Option<f32>,
 }
 
 impl From<TagModel> for Tag {
@@ -18,6 +26,10 @@ impl From<TagModel> for Tag {
         Self {
             id: value.id,
             name: value.name,
+            max_lifetime_years: value.max_lifetime_years,
+            max_service_years: value.max_service_years,
+            inspection_after_falls: value.inspection_after_falls,
+            inspection_after_fall_factor: value.inspection_after_fall_factor,
         }
     }
 }
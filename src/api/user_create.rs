@@ -22,6 +22,7 @@ pub struct CreateUser {
     perm_items: bool,
     perm_action_inspect: bool,
     perm_action_lend: bool,
+    perm_action_maintain: bool,
 }
 
 pub async fn handler(
@@ -36,6 +37,7 @@ pub async fn handler(
         perm_tags,
         perm_action_lend,
         perm_action_inspect,
+        perm_action_maintain,
     }): Json<CreateUser>,
 ) -> ApiResult<Json<UserWithPermissions>> {
     let password = tokio::task::spawn_blocking(move || {
@@ -60,6 +62,7 @@ pub async fn handler(
             perm_items,
             perm_action_inspect,
             perm_action_lend,
+            perm_action_maintain,
         })
         .returning(users::all_columns)
         .get_result::<UserModel>(&mut conn)
@@ -23,6 +23,8 @@ pub struct UserWithPermissions {
     perm_action_inspect: bool,
     /// User has permission to lend items
     perm_action_lend: bool,
+    /// User has permission to maintain items
+    perm_action_maintain: bool,
 }
 
 impl From<UserModel> for UserWithPermissions {
@@ -36,6 +38,7 @@ impl From<UserModel> for UserWithPermissions {
             perm_items: value.perm_items,
             perm_action_inspect: value.perm_action_inspect,
             perm_action_lend: value.perm_action_lend,
+            perm_action_maintain: value.perm_action_maintain,
         }
     }
 }
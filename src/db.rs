@@ -41,3 +41,35 @@ macro_rules! diesel_json {
         }
     };
 }
+
+macro_rules! diesel_text {
+    ($t:ty) => {
+        impl diesel::Queryable<Text, Pg> for $t {
+            type Row = String;
+
+            fn build(row: Self::Row) -> diesel::deserialize::Result<Self> {
+                Ok(serde_json::from_value(serde_json::Value::String(row))?)
+            }
+        }
+        impl diesel::deserialize::FromSql<Text, Pg> for $t {
+            fn from_sql(
+                bytes: <Pg as diesel::backend::Backend>::RawValue<'_>,
+            ) -> diesel::deserialize::Result<Self> {
+                let v = <String as diesel::deserialize::FromSql<Text, Pg>>::from_sql(bytes)?;
+                Ok(serde_json::from_value(serde_json::Value::String(v))?)
+            }
+        }
+        impl diesel::serialize::ToSql<Text, Pg> for $t {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut diesel::serialize::Output<'b, '_, Pg>,
+            ) -> diesel::serialize::Result {
+                let v = match serde_json::to_value(self)? {
+                    serde_json::Value::String(v) => v,
+                    v => return Err(format!("{v} cannot be stored as text").into()),
+                };
+                <String as diesel::serialize::ToSql<Text, Pg>>::to_sql(&v, &mut out.reborrow())
+            }
+        }
+    };
+}
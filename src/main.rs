@@ -7,17 +7,26 @@ pub mod provisioning;
 pub mod schema;
 
 use axum::{
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use diesel::Connection;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
 
 use api::{
-    item_create, item_details, item_inspect, item_list, r#static, tag_create, tag_delete, tag_list,
-    user_create, user_delete, user_list, user_login, Application,
+    event_amend, event_void, inspections_due, item_actions, item_archive, item_borrow,
+    item_by_serial, item_create, item_details, item_duplicate_serials, item_found,
+    item_historical_event, item_inspect, item_list, item_lose, item_maintain, item_purge,
+    item_quarantine, item_restore, item_retire, item_return, item_update, item_use, kit_add_item,
+    kit_borrow, kit_close, kit_create, kit_inspect, kit_list, kit_remove_item, kit_return,
+    kit_template_availability, kit_template_create, kit_template_delete, kit_template_list,
+    lifecycle, loan_create, loan_list, loan_return, member_create, member_delete, member_list,
+    member_loans, member_merge, member_update, r#static, reservation_calendar, reservation_create,
+    reservation_delete, search, tag_create, tag_delete, tag_list, user_create, user_delete,
+    user_list, user_login, Application,
 };
 use db::create_pool;
+use models::lifecycle::Lifecycle;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 use diesel_migrations::MigrationHarness;
@@ -63,6 +72,18 @@ async fn main(#[shuttle_shared_db::Postgres] db_url: String) -> shuttle_axum::Sh
     let db_pool = create_pool(&db_url);
     run_migrations_url(db_url.clone()).await.unwrap();
 
+    // Clubs can tighten the item lifecycle with a JSON file
+    if let Ok(path) = std::env::var("SAFEGEAR_LIFECYCLE") {
+        tracing::info!("Loading lifecycle from {path}");
+        Lifecycle::load(&path)
+            .and_then(Lifecycle::install)
+            .map_err(|e| {
+                tracing::error!("Failed to configure lifecycle: {e}");
+                e
+            })
+            .unwrap();
+    }
+
     #[cfg(debug_assertions)]
     {
         provisioning::provision(&mut db_pool.get().await.unwrap())
@@ -82,8 +103,74 @@ async fn main(#[shuttle_shared_db::Postgres] db_url: String) -> shuttle_axum::Sh
         .route("/", get(r#static::index_handler))
         .route("/api/items", get(item_list::handler))
         .route("/api/items", post(item_create::handler))
+        .route("/api/items/by-serial/:serial", get(item_by_serial::handler))
+        .route(
+            "/api/items/duplicate-serials",
+            get(item_duplicate_serials::handler),
+        )
         .route("/api/items/:id", get(item_details::handler))
+        .route("/api/items/:id", patch(item_update::handler))
+        .route("/api/items/:id", delete(item_archive::handler))
+        .route("/api/items/:id/restore", post(item_restore::handler))
+        .route("/api/items/:id/purge", post(item_purge::handler))
+        .route("/api/items/:id/actions", get(item_actions::handler))
         .route("/api/items/:id/events/inspect", post(item_inspect::handler))
+        .route(
+            "/api/items/:id/events/historical",
+            post(item_historical_event::handler),
+        )
+        .route("/api/items/:id/events/borrow", post(item_borrow::handler))
+        .route("/api/items/:id/events/return", post(item_return::handler))
+        .route("/api/items/:id/events/retire", post(item_retire::handler))
+        .route("/api/items/:id/events/lose", post(item_lose::handler))
+        .route("/api/items/:id/events/found", post(item_found::handler))
+        .route("/api/items/:id/events/use", post(item_use::handler))
+        .route(
+            "/api/items/:id/events/maintain",
+            post(item_maintain::handler),
+        )
+        .route(
+            "/api/items/:id/events/quarantine",
+            post(item_quarantine::handler),
+        )
+        .route("/api/events/:id/void", post(event_void::handler))
+        .route("/api/events/:id/amend", post(event_amend::handler))
+        .route("/api/inspections/due", get(inspections_due::handler))
+        .route("/api/kits", get(kit_list::handler))
+        .route("/api/kits", post(kit_create::handler))
+        .route("/api/kits/:id/items", post(kit_add_item::handler))
+        .route(
+            "/api/kits/:id/items/:item_id",
+            delete(kit_remove_item::handler),
+        )
+        .route("/api/kits/:id/close", post(kit_close::handler))
+        .route("/api/kits/:id/events/borrow", post(kit_borrow::handler))
+        .route("/api/kits/:id/events/return", post(kit_return::handler))
+        .route("/api/kits/:id/events/inspect", post(kit_inspect::handler))
+        .route("/api/kit-templates", get(kit_template_list::handler))
+        .route("/api/kit-templates", post(kit_template_create::handler))
+        .route(
+            "/api/kit-templates/:id",
+            delete(kit_template_delete::handler),
+        )
+        .route(
+            "/api/kit-templates/:id/availability",
+            get(kit_template_availability::handler),
+        )
+        .route("/api/lifecycle", get(lifecycle::handler))
+        .route("/api/loans", get(loan_list::handler))
+        .route("/api/loans", post(loan_create::handler))
+        .route("/api/loans/return", post(loan_return::handler))
+        .route("/api/members", get(member_list::handler))
+        .route("/api/members", post(member_create::handler))
+        .route("/api/members/:id", put(member_update::handler))
+        .route("/api/members/:id", delete(member_delete::handler))
+        .route("/api/members/:id/loans", get(member_loans::handler))
+        .route("/api/members/:id/merge", post(member_merge::handler))
+        .route("/api/reservations", get(reservation_calendar::handler))
+        .route("/api/reservations", post(reservation_create::handler))
+        .route("/api/reservations/:id", delete(reservation_delete::handler))
+        .route("/api/search", get(search::handler))
         .route("/api/tags", get(tag_list::handler))
         .route("/api/tags", post(tag_create::handler))
         .route("/api/tags/:id", delete(tag_delete::handler))
@@ -1,12 +1,24 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
 use diesel::{
-    expression::AsExpression, pg::Pg, sql_types::Jsonb, Associations, ExpressionMethods as _,
-    Identifiable, Insertable, OptionalExtension as _, QueryDsl as _, Queryable, Selectable,
+    expression::AsExpression, pg::Pg, sql_types::Jsonb, Associations, BelongingToDsl as _,
+    ExpressionMethods as _, Identifiable, Insertable, QueryDsl as _, Queryable, Selectable,
+    SelectableHelper as _,
 };
 use diesel_async::{scoped_futures::ScopedFutureExt as _, AsyncConnection as _, RunQueryDsl as _};
 use serde::{Deserialize, Serialize};
 
-use crate::{api::ApiError, models::item::Item, schema::*};
+use crate::{
+    api::ApiError,
+    models::{
+        item::{Item, ItemState, ItemStatus},
+        lifecycle::Lifecycle,
+        reservation::Reservation,
+        tag::{ItemTag, Tag},
+    },
+    schema::*,
+};
 
 #[derive(Insertable)]
 #[diesel(table_name = events)]
@@ -20,7 +32,7 @@ struct InsertEvent {
 #[diesel(belongs_to(Item))]
 pub struct Event {
     pub id: i64,
-    item_id: i64,
+    pub item_id: i64,
     pub ts: chrono::NaiveDateTime,
     pub data: EventData,
 }
@@ -32,55 +44,343 @@ impl Event {
         ts: chrono::DateTime<Utc>,
         data: EventData,
     ) -> Result<Event, ApiError> {
+        data.check_details()?;
         conn.transaction(|conn| {
             async {
-                let last_event: Option<Self> = events::table
-                    .filter(events::item_id.eq(item_id))
-                    .order_by(events::ts.desc())
-                    .limit(1)
-                    .get_result(conn)
-                    .await
-                    .optional()?;
+                // lock the item until the end of the transaction, so that concurrent
+                // events on the same item are checked one after the other
+                let item = items::table
+                    .find(item_id)
+                    .for_update()
+                    .get_result::<Item>(conn)
+                    .await?;
+
+                // archived items only keep their history
+                if item.archived_ts.is_some() {
+                    return Err(ApiError::ItemArchived(item_id));
+                }
 
                 // cannot insert an event before another
-                if let Some(ref last_event) = last_event {
-                    if ts <= last_event.ts.and_utc() {
-                        return Err(ApiError::InvalidEventTime(last_event.ts.and_utc(), ts));
+                if let Some(last_event_ts) = item.last_event_ts {
+                    if ts <= last_event_ts.and_utc() {
+                        return Err(ApiError::InvalidEventTime(last_event_ts.and_utc(), ts));
                     }
                 }
 
                 // only allow specific event successions
                 // (ex: can only lose or return an item, after a borrow)
-                if !EventData::check_transition(
-                    match &last_event {
-                        Some(ref value) => Some(&value.data),
-                        None => None,
-                    },
-                    &data,
-                ) {
-                    Err(ApiError::InvalidTransition(
-                        last_event.map(|event| event.data),
-                        data,
-                    ))
-                } else {
-                    Ok(InsertEvent {
-                        item_id,
-                        ts: ts.naive_utc(),
-                        data,
+                if !EventData::check_transition(item.status, &data) {
+                    return Err(ApiError::InvalidTransition(item.status, data));
+                }
+
+                if let EventData::Borrowed {
+                    borrower_id,
+                    due_back,
+                    ..
+                } = &data
+                {
+                    data.check_due_back(ts.naive_utc())?;
+                    Self::check_loan(
+                        conn,
+                        &item,
+                        *borrower_id,
+                        ts.naive_utc(),
+                        due_back.map(|due_back| due_back.naive_utc()),
+                    )
+                    .await?;
+                }
+
+                let mut item_state = item.state();
+                data.apply(ts.naive_utc(), &mut item_state);
+                let event: Self = InsertEvent {
+                    item_id,
+                    ts: ts.naive_utc(),
+                    data,
+                }
+                .insert_into(events::table)
+                .returning(events::all_columns)
+                .get_result(conn)
+                .await?;
+
+                // keep the item state in sync with its last event
+                diesel::update(items::table.find(item_id))
+                    .set(item_state)
+                    .execute(conn)
+                    .await?;
+
+                Ok(event)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Check that `item` can be lent to `borrower_id` from `ts` until `until`,
+    /// or until further notice if unset
+    async fn check_loan(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item: &Item,
+        borrower_id: i64,
+        ts: chrono::NaiveDateTime,
+        until: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), ApiError> {
+        // the loan must not overlap the reservation of another member
+        if let Some((reservation_id, starts_ts)) =
+            Reservation::held_by_other(conn, item.id, borrower_id, ts, until).await?
+        {
+            return Err(ApiError::ItemReserved(
+                item.id,
+                reservation_id,
+                chrono::DateTime::from_naive_utc_and_offset(starts_ts, Utc),
+            ));
+        }
+
+        // items past their end of life must not be lent anymore
+        let tags = ItemTag::belonging_to(item)
+            .inner_join(tags::table)
+            .select(Tag::as_select())
+            .get_results::<Tag>(conn)
+            .await?;
+        if let Some(end_of_life) = item.end_of_life(&tags) {
+            if ts >= end_of_life.naive_utc() {
+                return Err(ApiError::EndOfLife(end_of_life));
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert the same event for several items, all or nothing.
+    ///
+    /// Every item is checked even after a failure, so that all the rejected items
+    /// can be reported at once.
+    pub async fn insert_events(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item_ids: &[i64],
+        ts: chrono::DateTime<Utc>,
+        data: EventData,
+    ) -> Result<Vec<Event>, BatchError> {
+        // items are always locked in the same order, so that concurrent batches
+        // cannot deadlock, and an item listed twice only gets one event
+        let mut item_ids = item_ids.to_vec();
+        item_ids.sort_unstable();
+        item_ids.dedup();
+        conn.transaction(|conn| {
+            async {
+                let mut events = vec![];
+                let mut rejected = vec![];
+                for item_id in &item_ids {
+                    // each event runs in its own savepoint, so a rejected item
+                    // does not prevent checking the next ones
+                    match Self::insert_event(conn, *item_id, ts, data.clone()).await {
+                        Ok(event) => events.push(event),
+                        Err(error) => rejected.push((*item_id, error)),
                     }
-                    .insert_into(events::table)
-                    .returning(events::all_columns)
-                    .get_result(conn)
-                    .await?)
                 }
+                if rejected.is_empty() {
+                    Ok(events)
+                } else {
+                    Err(BatchError::Rejected(rejected))
+                }
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Insert an event which does not take part in the lifecycle of an item,
+    /// so that it leaves the item state untouched
+    pub(crate) async fn insert_audit_event(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item_id: i64,
+        ts: chrono::DateTime<Utc>,
+        data: EventData,
+    ) -> Result<Event, ApiError> {
+        let archived_ts = items::table
+            .find(item_id)
+            .select(items::archived_ts)
+            .for_update()
+            .get_result::<Option<chrono::NaiveDateTime>>(conn)
+            .await?;
+        if archived_ts.is_some() {
+            return Err(ApiError::ItemArchived(item_id));
+        }
+        Ok(InsertEvent {
+            item_id,
+            ts: ts.naive_utc(),
+            data,
+        }
+        .insert_into(events::table)
+        .returning(events::all_columns)
+        .get_result(conn)
+        .await?)
+    }
+
+    /// Insert an event anywhere in the history of an item.
+    ///
+    /// The whole history is then replayed, and the event is only kept if every
+    /// transition is still valid.
+    pub async fn insert_historical_event(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item_id: i64,
+        ts: chrono::DateTime<Utc>,
+        data: EventData,
+    ) -> Result<Event, ApiError> {
+        data.check_details()?;
+        conn.transaction(|conn| {
+            async {
+                let item = items::table
+                    .find(item_id)
+                    .for_update()
+                    .get_result::<Item>(conn)
+                    .await?;
+                if item.archived_ts.is_some() {
+                    return Err(ApiError::ItemArchived(item_id));
+                }
+                let event: Self = InsertEvent {
+                    item_id,
+                    ts: ts.naive_utc(),
+                    data,
+                }
+                .insert_into(events::table)
+                .returning(events::all_columns)
+                .get_result(conn)
+                .await?;
+
+                Self::replay(conn, &item).await?;
+
+                Ok(event)
             }
             .scope_boxed()
         })
         .await
     }
+
+    /// History of an item once corrections are applied.
+    ///
+    /// Events outside of the lifecycle and voided events are left out, amended events come with the details
+    /// of their latest amendment. `events` must be in chronological order.
+    pub fn effective_history(events: &[Event]) -> Vec<(&Event, &EventData)> {
+        let mut corrections: HashMap<i64, Option<&EventData>> = HashMap::new();
+        for event in events {
+            match &event.data {
+                EventData::Voided {
+                    target_event_id, ..
+                } => {
+                    corrections.insert(*target_event_id, None);
+                }
+                EventData::Amended {
+                    target_event_id,
+                    data,
+                    ..
+                } => {
+                    // a voided event stays voided
+                    if let Some(amended) = corrections
+                        .entry(*target_event_id)
+                        .or_insert(Some(data.as_ref()))
+                    {
+                        *amended = data.as_ref();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        events
+            .iter()
+            .filter(|event| event.data.kind().is_lifecycle())
+            .filter_map(|event| match corrections.get(&event.id) {
+                None => Some((event, &event.data)),
+                Some(Some(amended)) => Some((event, *amended)),
+                Some(None) => None,
+            })
+            .collect()
+    }
+
+    /// Recompute the state of an item from its whole history, checking every transition and loan
+    pub(crate) async fn replay(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item: &Item,
+    ) -> Result<(), ApiError> {
+        let events = Event::belonging_to(item)
+            .order_by((events::ts.asc(), events::id.asc()))
+            .get_results::<Event>(conn)
+            .await?;
+
+        // corrections must target a lifecycle event of the same item
+        for event in &events {
+            if let Some(target_event_id) = event.data.corrected_event() {
+                let targets_regular_event = events.iter().any(|target| {
+                    target.id == target_event_id && target.data.kind().is_lifecycle()
+                });
+                let amends_with_correction = matches!(
+                    &event.data,
+                    EventData::Amended { data, .. } if !data.kind().is_lifecycle()
+                );
+                if !targets_regular_event || amends_with_correction {
+                    return Err(ApiError::InvalidCorrection(target_event_id));
+                }
+            }
+        }
+
+        let mut item_state = ItemState::default();
+        let mut loans = vec![];
+        for (event, data) in Self::effective_history(&events) {
+            if !EventData::check_transition(item_state.status, data) {
+                return Err(ApiError::InvalidTransition(item_state.status, data.clone()));
+            }
+            if let EventData::Borrowed {
+                borrower_id,
+                due_back,
+                ..
+            } = data
+            {
+                data.check_due_back(event.ts)?;
+                loans.push((
+                    *borrower_id,
+                    event.ts,
+                    due_back.map(|due_back| due_back.naive_utc()),
+                ));
+            }
+            let was_borrowed = item_state.status == ItemStatus::Borrowed;
+            data.apply(event.ts, &mut item_state);
+            // loans are checked with the window of the live borrow, finished loans
+            // without a due date only cover the time until the item came back
+            if was_borrowed && item_state.status != ItemStatus::Borrowed {
+                if let Some((_, _, until @ None)) = loans.last_mut() {
+                    *until = Some(event.ts);
+                }
+            }
+        }
+
+        diesel::update(items::table.find(item.id))
+            .set(item_state)
+            .execute(conn)
+            .await?;
+
+        // loans are checked like live borrows, against the replayed state
+        let item = items::table.find(item.id).get_result::<Item>(conn).await?;
+        for (borrower_id, ts, until) in loans {
+            Self::check_loan(conn, &item, borrower_id, ts, until).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Error while inserting a batch of events
+pub enum BatchError {
+    /// The batch could not be processed at all
+    Api(ApiError),
+    /// Some items refused the event, nothing was recorded
+    Rejected(Vec<(i64, ApiError)>),
 }
 
-#[derive(ts_rs::TS, Serialize, Deserialize, Debug)]
+impl From<diesel::result::Error> for BatchError {
+    fn from(value: diesel::result::Error) -> Self {
+        BatchError::Api(value.into())
+    }
+}
+
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone)]
 pub enum InspectionResult {
     /// Item is new or in very good condition
     Good,
@@ -92,7 +392,61 @@ pub enum InspectionResult {
     Danger,
 }
 
-#[derive(ts_rs::TS, Serialize, Deserialize, Debug, AsExpression)]
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone)]
+pub enum RemovalReason {
+    /// Item reached the end of its life through normal use
+    WornOut,
+    /// Item did not pass an inspection
+    FailedInspection,
+    /// Item held a severe fall
+    Fall,
+    /// Item was recalled by its manufacturer
+    Recall,
+    /// Item exceeded its maximum age
+    AgeLimit,
+    /// Item was stolen
+    Stolen,
+    /// Reason is not known
+    Unknown,
+}
+
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone)]
+pub enum Disposal {
+    /// Item was destroyed
+    Destroyed,
+    /// Item was cut up so it cannot be used anymore
+    CutUp,
+    /// Item is kept for training purposes only, never for real use
+    KeptForTraining,
+}
+
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone)]
+pub enum UsageCondition {
+    /// Item was used in wet conditions
+    Wet,
+    /// Item was used in icy conditions
+    Icy,
+    /// Item was loaded over a sharp edge
+    SharpEdge,
+}
+
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone)]
+pub enum MaintenanceKind {
+    /// Item was washed
+    Washed,
+    /// Item was cleaned
+    Cleaned,
+    /// Item was lubricated
+    Lubricated,
+    /// Item was repaired
+    Repaired,
+    /// A part of the item was replaced
+    PartReplaced,
+    /// Any other maintenance, see the comment
+    Other,
+}
+
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone, AsExpression)]
 #[diesel(sql_type = Jsonb)]
 #[repr(u8)]
 #[serde(tag = "kind")]
@@ -112,10 +466,15 @@ pub enum EventData {
     } = 2,
     /// Event logged when someone borrows an item
     Borrowed {
-        /// Person who borrowed the item
+        /// Member who borrowed the item
+        borrower_id: i64,
+        /// Name of the member at the time of the borrow
         borrower: String,
         /// Person who validated the borrow
         validator: String,
+        /// Time the item is expected back
+        #[serde(default)]
+        due_back: Option<chrono::DateTime<Utc>>,
     } = 3,
     /// Event logged when an item is returned after a borrow
     Returned {
@@ -123,114 +482,383 @@ pub enum EventData {
         validator: String,
     } = 4,
     /// Event logged when the item is retired
-    Retired {} = 5,
-    /// Event logged when the item is retired
-    Lost {} = 6,
+    Retired {
+        /// Why the item was retired
+        reason: RemovalReason,
+        /// What was done with the item after retirement
+        disposal: Disposal,
+        /// Optional comment about the retirement
+        comment: Option<String>,
+        /// Person who validated the retirement
+        validator: String,
+    } = 5,
+    /// Event logged when the item is declared lost
+    Lost {
+        /// Why the item is considered lost
+        reason: RemovalReason,
+        /// Optional comment about the loss
+        comment: Option<String>,
+        /// Person who declared the item lost
+        validator: String,
+    } = 6,
+    /// Event logged when a lost item is found again
+    Found {
+        /// Person who found the item
+        finder: String,
+        /// Where the item was found
+        location: String,
+        /// Person who validated the recovery
+        validator: String,
+    } = 7,
+    /// Event logged when an item was used during a session
+    Used {
+        /// Day of the session
+        session_date: chrono::NaiveDate,
+        /// Number of falls held by the item
+        falls: u32,
+        /// Worst fall factor held by the item, if known
+        worst_fall_factor: Option<f32>,
+        /// Conditions the item was used in
+        conditions: Vec<UsageCondition>,
+        /// Optional comment about the session
+        comment: Option<String>,
+        /// Person who reported the usage
+        reporter: String,
+    } = 8,
+    /// Event logged when maintenance was done on the item
+    Maintained {
+        /// Kind of maintenance
+        maintenance: MaintenanceKind,
+        /// Person who did the maintenance
+        performer: String,
+        /// Optional comment about the maintenance
+        comment: Option<String>,
+    } = 9,
+    /// Event logged when an item is put aside until it is inspected again
+    Quarantined {
+        /// Why the item was quarantined
+        reason: String,
+        /// Person who quarantined the item
+        validator: String,
+    } = 10,
+    /// Event logged when a previous event is cancelled, it is kept for audit only
+    Voided {
+        /// Id of the cancelled event
+        target_event_id: i64,
+        /// Why the event was cancelled
+        reason: String,
+        /// Person who cancelled the event
+        validator: String,
+    } = 11,
+    /// Event logged when a previous event is replaced by a corrected version
+    Amended {
+        /// Id of the corrected event
+        target_event_id: i64,
+        /// Corrected details of the event
+        data: Box<EventData>,
+        /// Why the event was corrected
+        reason: String,
+        /// Person who corrected the event
+        validator: String,
+    } = 12,
+    /// Event logged when the details of an item are edited, it is kept for audit only
+    Edited {
+        /// Fields changed by the edition
+        changes: Vec<FieldChange>,
+        /// Person who edited the item
+        editor: String,
+    } = 13,
+    /// Event logged when a past event is added to the history, it is kept for audit only
+    Recorded {
+        /// Id of the added event
+        target_event_id: i64,
+        /// Person who added the event
+        recorder: String,
+    } = 14,
+}
+
+/// Change of a single field of an item
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone)]
+pub struct FieldChange {
+    /// Name of the changed field
+    pub field: String,
+    /// Value before the change
+    pub old: Option<String>,
+    /// Value after the change
+    pub new: Option<String>,
 }
 diesel_json!(EventData);
 
-#[derive(Default)]
-pub(crate) struct Transition {
-    manufactured: bool,
-    put_into_service: bool,
-    inspected: bool,
-    borrowed: bool,
-    returned: bool,
-    retired: bool,
-    lost: bool,
+/// Kind of an event, without its details
+#[derive(ts_rs::TS, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Manufactured,
+    PutIntoService,
+    Inspected,
+    Borrowed,
+    Returned,
+    Retired,
+    Lost,
+    Found,
+    Used,
+    Maintained,
+    Quarantined,
+    Voided,
+    Amended,
+    Edited,
+    Recorded,
 }
-impl Transition {
-    fn get_value(&self, event: &EventData) -> bool {
-        match event {
-            EventData::Manufactured {} => self.manufactured,
-            EventData::PutIntoService {} => self.put_into_service,
-            EventData::Inspected { .. } => self.inspected,
-            EventData::Borrowed { .. } => self.borrowed,
-            EventData::Returned { .. } => self.returned,
-            EventData::Retired {} => self.retired,
-            EventData::Lost {} => self.lost,
-        }
+
+impl EventKind {
+    pub const ALL: [EventKind; 15] = [
+        EventKind::Manufactured,
+        EventKind::PutIntoService,
+        EventKind::Inspected,
+        EventKind::Borrowed,
+        EventKind::Returned,
+        EventKind::Retired,
+        EventKind::Lost,
+        EventKind::Found,
+        EventKind::Used,
+        EventKind::Maintained,
+        EventKind::Quarantined,
+        EventKind::Voided,
+        EventKind::Amended,
+        EventKind::Edited,
+        EventKind::Recorded,
+    ];
+
+    /// Whether events of this kind take part in the lifecycle of the item,
+    /// instead of correcting another event or auditing the item
+    pub fn is_lifecycle(&self) -> bool {
+        !matches!(
+            self,
+            EventKind::Voided | EventKind::Amended | EventKind::Edited | EventKind::Recorded
+        )
     }
 }
 
 impl EventData {
-    fn get_transition(last_event: Option<&Self>) -> Transition {
-        match last_event {
-            None => Transition {
-                manufactured: true,
-                put_into_service: false,
-                inspected: false,
-                borrowed: false,
-                returned: false,
-                retired: false,
-                lost: false,
-            },
-            Some(EventData::Manufactured {}) => Transition {
-                manufactured: false,
-                put_into_service: true,
-                inspected: false,
-                borrowed: false,
-                returned: false,
-                retired: true,
-                lost: true,
-            },
-            Some(EventData::PutIntoService {}) => Transition {
-                manufactured: false,
-                put_into_service: false,
-                inspected: true,
-                borrowed: true,
-                returned: false,
-                retired: true,
-                lost: true,
-            },
-            Some(EventData::Inspected { .. }) => Transition {
-                manufactured: false,
-                put_into_service: false,
-                inspected: true,
-                borrowed: true,
-                returned: false,
-                retired: true,
-                lost: true,
-            },
-            Some(EventData::Borrowed { .. }) => Transition {
-                manufactured: false,
-                put_into_service: false,
-                inspected: false,
-                borrowed: false,
-                returned: true,
-                retired: false,
-                lost: true,
-            },
-            Some(EventData::Returned { .. }) => Transition {
-                manufactured: false,
-                put_into_service: false,
-                inspected: true,
-                borrowed: true,
-                returned: false,
-                retired: true,
-                lost: true,
-            },
-            Some(EventData::Retired { .. }) => Transition {
-                manufactured: false,
-                put_into_service: false,
-                inspected: false,
-                borrowed: false,
-                returned: false,
-                retired: false,
-                lost: false,
-            },
-            Some(EventData::Lost { .. }) => Transition {
-                manufactured: false,
-                put_into_service: false,
-                inspected: false,
-                borrowed: false,
-                returned: false,
-                retired: false,
-                lost: false,
-            },
-        }
-    }
-    pub(crate) fn check_transition(last_event: Option<&Self>, next_event: &Self) -> bool {
-        Self::get_transition(last_event).get_value(next_event)
+    pub fn kind(&self) -> EventKind {
+        match self {
+            EventData::Manufactured {} => EventKind::Manufactured,
+            EventData::PutIntoService {} => EventKind::PutIntoService,
+            EventData::Inspected { .. } => EventKind::Inspected,
+            EventData::Borrowed { .. } => EventKind::Borrowed,
+            EventData::Returned { .. } => EventKind::Returned,
+            EventData::Retired { .. } => EventKind::Retired,
+            EventData::Lost { .. } => EventKind::Lost,
+            EventData::Found { .. } => EventKind::Found,
+            EventData::Used { .. } => EventKind::Used,
+            EventData::Maintained { .. } => EventKind::Maintained,
+            EventData::Quarantined { .. } => EventKind::Quarantined,
+            EventData::Voided { .. } => EventKind::Voided,
+            EventData::Amended { .. } => EventKind::Amended,
+            EventData::Edited { .. } => EventKind::Edited,
+            EventData::Recorded { .. } => EventKind::Recorded,
+        }
+    }
+    /// Event corrected by this event, if it is a correction
+    pub fn corrected_event(&self) -> Option<i64> {
+        match self {
+            EventData::Voided {
+                target_event_id, ..
+            }
+            | EventData::Amended {
+                target_event_id, ..
+            } => Some(*target_event_id),
+            _ => None,
+        }
+    }
+    /// Check the details of the event which do not depend on the item
+    pub(crate) fn check_details(&self) -> Result<(), ApiError> {
+        match self {
+            // a fall can at most be twice as long as the rope paid out
+            EventData::Used {
+                worst_fall_factor: Some(fall_factor),
+                ..
+            } if !(0.0..=2.0).contains(fall_factor) => {
+                Err(ApiError::InvalidFallFactor(*fall_factor))
+            }
+            EventData::Amended { data, .. } => data.check_details(),
+            _ => Ok(()),
+        }
+    }
+    /// Refuse a borrow expected back before it happens at `ts`
+    pub(crate) fn check_due_back(&self, ts: chrono::NaiveDateTime) -> Result<(), ApiError> {
+        match self {
+            EventData::Borrowed {
+                due_back: Some(due_back),
+                ..
+            } if due_back.naive_utc() <= ts => Err(ApiError::InvalidDueDate(*due_back)),
+            _ => Ok(()),
+        }
+    }
+    /// Keep the people recorded by `original`, which this event corrects.
+    ///
+    /// Returns whether the borrower changed, and its name must be taken again.
+    pub(crate) fn keep_people(&mut self, original: &EventData) -> bool {
+        match (self, original) {
+            (
+                EventData::Inspected { inspector, .. },
+                EventData::Inspected {
+                    inspector: original,
+                    ..
+                },
+            ) => inspector.clone_from(original),
+            (
+                EventData::Borrowed {
+                    borrower_id,
+                    borrower,
+                    validator,
+                    ..
+                },
+                EventData::Borrowed {
+                    borrower_id: original_borrower_id,
+                    borrower: original_borrower,
+                    validator: original_validator,
+                    ..
+                },
+            ) => {
+                validator.clone_from(original_validator);
+                if borrower_id != original_borrower_id {
+                    return true;
+                }
+                borrower.clone_from(original_borrower);
+            }
+            (
+                EventData::Returned { validator },
+                EventData::Returned {
+                    validator: original,
+                },
+            )
+            | (
+                EventData::Retired { validator, .. },
+                EventData::Retired {
+                    validator: original,
+                    ..
+                },
+            )
+            | (
+                EventData::Lost { validator, .. },
+                EventData::Lost {
+                    validator: original,
+                    ..
+                },
+            )
+            | (
+                EventData::Found { validator, .. },
+                EventData::Found {
+                    validator: original,
+                    ..
+                },
+            )
+            | (
+                EventData::Quarantined { validator, .. },
+                EventData::Quarantined {
+                    validator: original,
+                    ..
+                },
+            ) => validator.clone_from(original),
+            (
+                EventData::Used { reporter, .. },
+                EventData::Used {
+                    reporter: original, ..
+                },
+            ) => reporter.clone_from(original),
+            (
+                EventData::Maintained { performer, .. },
+                EventData::Maintained {
+                    performer: original,
+                    ..
+                },
+            ) => performer.clone_from(original),
+            _ => {}
+        }
+        false
+    }
+    /// Status of an item after this event, when it was in `status` before
+    pub(crate) fn resulting_status(&self, status: ItemStatus) -> ItemStatus {
+        match self {
+            EventData::Manufactured {} => ItemStatus::InStock,
+            EventData::PutIntoService {} => ItemStatus::InService,
+            // worrying inspections put the item aside until the next inspection
+            EventData::Inspected {
+                result: InspectionResult::Warning | InspectionResult::Danger,
+                ..
+            } => ItemStatus::Quarantined,
+            EventData::Inspected { .. } => ItemStatus::InService,
+            EventData::Borrowed { .. } => ItemStatus::Borrowed,
+            EventData::Returned { .. } => ItemStatus::InService,
+            EventData::Retired { .. } => ItemStatus::Retired,
+            EventData::Lost { .. } => ItemStatus::Lost,
+            EventData::Found { .. } => ItemStatus::Found,
+            EventData::Quarantined { .. } => ItemStatus::Quarantined,
+            EventData::Used { .. } => status,
+            EventData::Maintained { .. } => status,
+            // corrections and audits are never part of the status computation
+            EventData::Voided { .. }
+            | EventData::Amended { .. }
+            | EventData::Edited { .. }
+            | EventData::Recorded { .. } => status,
+        }
+    }
+    /// Whether this event restarts the inspection period of the item
+    pub(crate) fn is_inspection(&self) -> bool {
+        matches!(
+            self,
+            EventData::PutIntoService {} | EventData::Inspected { .. }
+        )
+    }
+    /// Update the state of an item when this event happens at `ts`
+    pub(crate) fn apply(&self, ts: chrono::NaiveDateTime, state: &mut ItemState) {
+        state.status = self.resulting_status(state.status);
+        state.last_event_ts = Some(ts);
+        match self {
+            EventData::Manufactured {} => state.manufactured_ts = Some(ts),
+            EventData::PutIntoService {} => state.in_service_ts = Some(ts),
+            EventData::Borrowed {
+                borrower_id,
+                borrower,
+                due_back,
+                ..
+            } => {
+                state.borrower_id = Some(*borrower_id);
+                state.borrower = Some(borrower.clone());
+                state.borrowed_ts = Some(ts);
+                state.due_back = due_back.map(|due_back| due_back.naive_utc());
+            }
+            EventData::Used {
+                falls,
+                worst_fall_factor,
+                ..
+            } => {
+                state.falls_since_inspection = state
+                    .falls_since_inspection
+                    .saturating_add(i32::try_from(*falls).unwrap_or(i32::MAX));
+                state.worst_fall_factor_since_inspection = state
+                    .worst_fall_factor_since_inspection
+                    .into_iter()
+                    .chain(*worst_fall_factor)
+                    .max_by(f32::total_cmp);
+                state.last_used_ts = Some(ts);
+            }
+            _ => {}
+        }
+        // the loan ends as soon as the item leaves the borrowed status
+        if state.status != ItemStatus::Borrowed {
+            state.borrower_id = None;
+            state.borrower = None;
+            state.borrowed_ts = None;
+            state.due_back = None;
+        }
+        if self.is_inspection() {
+            state.last_inspection_ts = Some(ts);
+            state.falls_since_inspection = 0;
+            state.worst_fall_factor_since_inspection = None;
+        }
+    }
+    pub(crate) fn check_transition(status: ItemStatus, next_event: &Self) -> bool {
+        Lifecycle::current().allows(status, next_event.kind())
     }
 }
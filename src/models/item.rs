@@ -1,13 +1,151 @@
-use diesel::{data_types::PgInterval, prelude::*};
+use chrono::{Months, NaiveDateTime, TimeDelta, Utc};
+use diesel::{
+    data_types::PgInterval, expression::AsExpression, pg::Pg, prelude::*, sql_types::Text,
+};
+use serde::{Deserialize, Serialize};
 
 use crate::schema::items;
 
+use super::tag::Tag;
+
 #[derive(Selectable, Identifiable, Queryable)]
 pub struct Item {
     pub id: i64,
     pub name: String,
     pub inspection_period_days: Option<PgInterval>,
     pub serial_number: Option<String>,
+    pub status: ItemStatus,
+    pub last_event_ts: Option<NaiveDateTime>,
+    pub last_inspection_ts: Option<NaiveDateTime>,
+    pub max_lifetime_years: Option<i32>,
+    pub max_service_years: Option<i32>,
+    pub manufactured_ts: Option<NaiveDateTime>,
+    pub in_service_ts: Option<NaiveDateTime>,
+    pub inspection_after_falls: Option<i32>,
+    pub inspection_after_fall_factor: Option<f32>,
+    pub falls_since_inspection: i32,
+    pub worst_fall_factor_since_inspection: Option<f32>,
+    pub last_used_ts: Option<NaiveDateTime>,
+    pub borrower: Option<String>,
+    pub borrowed_ts: Option<NaiveDateTime>,
+    pub due_back: Option<NaiveDateTime>,
+    pub borrower_id: Option<i64>,
+    pub archived_ts: Option<NaiveDateTime>,
+}
+
+impl Item {
+    /// Current derived state of the item
+    pub(crate) fn state(&self) -> ItemState {
+        ItemState {
+            status: self.status,
+            last_event_ts: self.last_event_ts,
+            last_inspection_ts: self.last_inspection_ts,
+            manufactured_ts: self.manufactured_ts,
+            in_service_ts: self.in_service_ts,
+            falls_since_inspection: self.falls_since_inspection,
+            worst_fall_factor_since_inspection: self.worst_fall_factor_since_inspection,
+            last_used_ts: self.last_used_ts,
+            borrower: self.borrower.clone(),
+            borrowed_ts: self.borrowed_ts,
+            due_back: self.due_back,
+            borrower_id: self.borrower_id,
+        }
+    }
+
+    /// Date after which the item must not be used anymore.
+    ///
+    /// Lifetimes set on the item take precedence over the ones of its tags,
+    /// otherwise the strictest tag lifetime applies.
+    pub fn end_of_life<'a>(
+        &self,
+        tags: impl IntoIterator<Item = &'a Tag> + Clone,
+    ) -> Option<chrono::DateTime<Utc>> {
+        let max_lifetime_years = self.max_lifetime_years.or_else(|| {
+            tags.clone()
+                .into_iter()
+                .filter_map(|tag| tag.max_lifetime_years)
+                .min()
+        });
+        let max_service_years = self.max_service_years.or_else(|| {
+            tags.into_iter()
+                .filter_map(|tag| tag.max_service_years)
+                .min()
+        });
+
+        let from_manufacture = add_years(self.manufactured_ts, max_lifetime_years);
+        let from_service = add_years(self.in_service_ts, max_service_years);
+        from_manufacture
+            .into_iter()
+            .chain(from_service)
+            .min()
+            .map(|end_of_life| chrono::DateTime::from_naive_utc_and_offset(end_of_life, Utc))
+    }
+
+    /// Whether the item was used hard enough since its last inspection to need a new one.
+    ///
+    /// Thresholds set on the item take precedence over the ones of its tags,
+    /// otherwise the strictest tag threshold applies.
+    pub fn usage_requires_inspection<'a>(
+        &self,
+        tags: impl IntoIterator<Item = &'a Tag> + Clone,
+    ) -> bool {
+        let after_falls = self.inspection_after_falls.or_else(|| {
+            tags.clone()
+                .into_iter()
+                .filter_map(|tag| tag.inspection_after_falls)
+                .min()
+        });
+        let after_fall_factor = self.inspection_after_fall_factor.or_else(|| {
+            tags.into_iter()
+                .filter_map(|tag| tag.inspection_after_fall_factor)
+                .min_by(f32::total_cmp)
+        });
+
+        after_falls.is_some_and(|after_falls| self.falls_since_inspection >= after_falls)
+            || after_fall_factor
+                .zip(self.worst_fall_factor_since_inspection)
+                .is_some_and(|(after_fall_factor, worst)| worst >= after_fall_factor)
+    }
+
+    /// Date when the item must be inspected next, if it needs inspections at all
+    pub fn next_inspection_due<'a>(
+        &self,
+        tags: impl IntoIterator<Item = &'a Tag> + Clone,
+    ) -> Option<chrono::DateTime<Utc>> {
+        let due = match self.status {
+            // a found or quarantined item must be inspected right away
+            ItemStatus::Found | ItemStatus::Quarantined => self.last_event_ts,
+            ItemStatus::InService | ItemStatus::Borrowed => {
+                let periodic = self
+                    .last_inspection_ts
+                    .zip(self.inspection_period_days.as_ref())
+                    .and_then(|(ts, period)| add_interval(ts, period));
+                // an item used too hard must be inspected right after that use
+                let after_use = self
+                    .usage_requires_inspection(tags)
+                    .then_some(self.last_used_ts)
+                    .flatten();
+                periodic.into_iter().chain(after_use).min()
+            }
+            _ => None,
+        };
+        due.map(|due| chrono::DateTime::from_naive_utc_and_offset(due, Utc))
+    }
+}
+
+fn add_years(ts: Option<NaiveDateTime>, years: Option<i32>) -> Option<NaiveDateTime> {
+    ts?.checked_add_months(Months::new(u32::try_from(years?).ok()?.checked_mul(12)?))
+}
+
+fn add_interval(ts: NaiveDateTime, interval: &PgInterval) -> Option<NaiveDateTime> {
+    let ts = if interval.months >= 0 {
+        ts.checked_add_months(Months::new(interval.months as u32))?
+    } else {
+        ts.checked_sub_months(Months::new(interval.months.unsigned_abs()))?
+    };
+    ts.checked_add_signed(
+        TimeDelta::days(interval.days.into()) + TimeDelta::microseconds(interval.microseconds),
+    )
 }
 
 #[derive(Insertable)]
@@ -16,4 +154,85 @@ pub struct InsertItem {
     pub name: String,
     pub inspection_period_days: Option<PgInterval>,
     pub serial_number: Option<String>,
+    pub max_lifetime_years: Option<i32>,
+    pub max_service_years: Option<i32>,
+    pub inspection_after_falls: Option<i32>,
+    pub inspection_after_fall_factor: Option<f32>,
+}
+
+/// State of an item derived from its events
+#[derive(AsChangeset)]
+#[diesel(table_name = items, treat_none_as_null = true)]
+pub(crate) struct ItemState {
+    pub status: ItemStatus,
+    pub last_event_ts: Option<NaiveDateTime>,
+    pub last_inspection_ts: Option<NaiveDateTime>,
+    pub manufactured_ts: Option<NaiveDateTime>,
+    pub in_service_ts: Option<NaiveDateTime>,
+    pub falls_since_inspection: i32,
+    pub worst_fall_factor_since_inspection: Option<f32>,
+    pub last_used_ts: Option<NaiveDateTime>,
+    pub borrower: Option<String>,
+    pub borrowed_ts: Option<NaiveDateTime>,
+    pub due_back: Option<NaiveDateTime>,
+    pub borrower_id: Option<i64>,
+}
+
+impl Default for ItemState {
+    /// State of an item without any event
+    fn default() -> Self {
+        Self {
+            status: ItemStatus::Registered,
+            last_event_ts: None,
+            last_inspection_ts: None,
+            manufactured_ts: None,
+            in_service_ts: None,
+            falls_since_inspection: 0,
+            worst_fall_factor_since_inspection: None,
+            last_used_ts: None,
+            borrower: None,
+            borrowed_ts: None,
+            due_back: None,
+            borrower_id: None,
+        }
+    }
+}
+
+/// Current status of an item, derived from its last event
+#[derive(
+    ts_rs::TS, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, AsExpression,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    /// Item has no event yet
+    Registered,
+    /// Item was manufactured but not put into service
+    InStock,
+    /// Item is in service and available
+    InService,
+    /// Item is currently borrowed
+    Borrowed,
+    /// Item was retired
+    Retired,
+    /// Item was declared lost
+    Lost,
+    /// Item was found after being lost, and waits for an inspection
+    Found,
+    /// Item is put aside until it passes an inspection
+    Quarantined,
+}
+diesel_text!(ItemStatus);
+
+impl ItemStatus {
+    pub const ALL: [ItemStatus; 8] = [
+        ItemStatus::Registered,
+        ItemStatus::InStock,
+        ItemStatus::InService,
+        ItemStatus::Borrowed,
+        ItemStatus::Retired,
+        ItemStatus::Lost,
+        ItemStatus::Found,
+        ItemStatus::Quarantined,
+    ];
 }
@@ -0,0 +1,106 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Associations, BelongingToDsl as _, ExpressionMethods as _, Identifiable, Insertable,
+    OptionalExtension as _, QueryDsl as _, Queryable, Selectable,
+};
+use diesel_async::RunQueryDsl as _;
+
+use crate::{api::ApiError, schema::*};
+
+use super::item::{Item, ItemStatus};
+
+#[derive(Identifiable, Queryable, Selectable)]
+pub struct Kit {
+    pub id: i64,
+    pub name: String,
+    pub closed_ts: Option<NaiveDateTime>,
+}
+
+impl Kit {
+    /// Status shared by all the items of a kit, unset if they differ
+    pub fn status(items: impl IntoIterator<Item = ItemStatus>) -> Option<ItemStatus> {
+        let mut items = items.into_iter();
+        let first = items.next()?;
+        items.all(|status| status == first).then_some(first)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = kits)]
+pub struct InsertKit {
+    pub name: String,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Associations)]
+#[diesel(belongs_to(Kit))]
+#[diesel(belongs_to(Item))]
+#[diesel(table_name = kits_items)]
+pub struct KitItem {
+    id: i64,
+    pub kit_id: i64,
+    pub item_id: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = kits_items)]
+pub struct InsertKitItem {
+    pub kit_id: i64,
+    pub item_id: i64,
+}
+
+impl Kit {
+    /// Items of an open kit, refusing kits which were taken apart
+    pub async fn open_item_ids(
+        conn: &mut diesel_async::AsyncPgConnection,
+        kit_id: i64,
+    ) -> Result<Vec<i64>, ApiError> {
+        let kit = kits::table.find(kit_id).get_result::<Kit>(conn).await?;
+        if kit.closed_ts.is_some() {
+            return Err(ApiError::KitClosed(kit_id));
+        }
+        Ok(KitItem::belonging_to(&kit)
+            .select(kits_items::item_id)
+            .order_by(kits_items::item_id.asc())
+            .get_results::<i64>(conn)
+            .await?)
+    }
+
+    /// Refuse unknown or archived items, and items which already belong to another open kit.
+    ///
+    /// Must run in the transaction adding the items to the kit.
+    pub async fn check_free_items(
+        conn: &mut diesel_async::AsyncPgConnection,
+        kit_id: i64,
+        item_ids: &[i64],
+    ) -> Result<(), ApiError> {
+        // lock the items so that they cannot be added to two kits at once
+        let found = items::table
+            .filter(items::id.eq_any(item_ids))
+            .select((items::id, items::archived_ts))
+            .for_update()
+            .load::<(i64, Option<NaiveDateTime>)>(conn)
+            .await?;
+        if let Some(item_id) = item_ids
+            .iter()
+            .find(|item_id| !found.iter().any(|(id, _)| id == *item_id))
+        {
+            return Err(ApiError::UnknownItem(*item_id));
+        }
+        if let Some((item_id, _)) = found.iter().find(|(_, archived_ts)| archived_ts.is_some()) {
+            return Err(ApiError::ItemArchived(*item_id));
+        }
+        let taken = kits_items::table
+            .inner_join(kits::table)
+            .filter(kits::closed_ts.is_null())
+            .filter(kits_items::kit_id.ne(kit_id))
+            .filter(kits_items::item_id.eq_any(item_ids))
+            .select((kits_items::item_id, kits_items::kit_id))
+            .first::<(i64, i64)>(conn)
+            .await
+            .optional()?;
+        match taken {
+            Some((item_id, other_kit_id)) => Err(ApiError::ItemInOpenKit(item_id, other_kit_id)),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use diesel::prelude::*;
+
+use crate::schema::*;
+
+use super::tag::Tag;
+
+#[derive(Identifiable, Queryable, Selectable)]
+pub struct KitTemplate {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = kit_templates)]
+pub struct InsertKitTemplate {
+    pub name: String,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Associations)]
+#[diesel(belongs_to(KitTemplate))]
+#[diesel(belongs_to(Tag))]
+#[diesel(table_name = kit_templates_tags)]
+pub struct KitTemplateTag {
+    id: i64,
+    pub kit_template_id: i64,
+    pub tag_id: i64,
+    pub quantity: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = kit_templates_tags)]
+pub struct InsertKitTemplateTag {
+    pub kit_template_id: i64,
+    pub tag_id: i64,
+    pub quantity: i32,
+}
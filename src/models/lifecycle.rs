@@ -0,0 +1,117 @@
+use std::{collections::HashMap, path::Path, sync::OnceLock};
+
+use super::{event::EventKind, item::ItemStatus};
+
+/// Events allowed for each item status, unless configured otherwise
+const DEFAULT_LIFECYCLE: &[(ItemStatus, &[EventKind])] = &[
+    (ItemStatus::Registered, &[EventKind::Manufactured]),
+    (
+        ItemStatus::InStock,
+        &[
+            EventKind::PutIntoService,
+            EventKind::Retired,
+            EventKind::Lost,
+        ],
+    ),
+    (
+        ItemStatus::InService,
+        &[
+            EventKind::Inspected,
+            EventKind::Borrowed,
+            EventKind::Retired,
+            EventKind::Lost,
+            EventKind::Used,
+            EventKind::Maintained,
+            EventKind::Quarantined,
+        ],
+    ),
+    (
+        ItemStatus::Borrowed,
+        &[EventKind::Returned, EventKind::Lost, EventKind::Used],
+    ),
+    (ItemStatus::Retired, &[]),
+    (ItemStatus::Lost, &[EventKind::Found]),
+    // a found item must be inspected before it can be borrowed again
+    (
+        ItemStatus::Found,
+        &[
+            EventKind::Inspected,
+            EventKind::Retired,
+            EventKind::Lost,
+            EventKind::Quarantined,
+        ],
+    ),
+    // a quarantined item can only be released by an inspection, or retired
+    (
+        ItemStatus::Quarantined,
+        &[EventKind::Inspected, EventKind::Retired],
+    ),
+];
+
+static LIFECYCLE: OnceLock<Lifecycle> = OnceLock::new();
+
+#[derive(thiserror::Error, Debug)]
+pub enum LifecycleError {
+    #[error("Cannot read lifecycle configuration: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid lifecycle configuration: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Lifecycle configuration allows {1:?} for status {0:?}, which is never allowed")]
+    Loosened(ItemStatus, EventKind),
+    #[error("Lifecycle is already configured")]
+    AlreadyConfigured,
+}
+
+/// Events allowed for each item status
+pub struct Lifecycle(HashMap<ItemStatus, Vec<EventKind>>);
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self(
+            DEFAULT_LIFECYCLE
+                .iter()
+                .map(|(status, kinds)| (*status, kinds.to_vec()))
+                .collect(),
+        )
+    }
+}
+
+impl Lifecycle {
+    /// Lifecycle in use, the default one unless another one was installed at startup
+    pub fn current() -> &'static Self {
+        LIFECYCLE.get_or_init(Self::default)
+    }
+
+    /// Load a lifecycle from a JSON file mapping statuses to their allowed events.
+    ///
+    /// Statuses missing from the file keep their default events. The file can only
+    /// remove events from the default lifecycle, not add new ones.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LifecycleError> {
+        let overrides: HashMap<ItemStatus, Vec<EventKind>> =
+            serde_json::from_slice(&std::fs::read(path)?)?;
+        let mut lifecycle = Self::default();
+        for (status, kinds) in overrides {
+            if let Some(kind) = kinds.iter().find(|kind| !lifecycle.allows(status, **kind)) {
+                return Err(LifecycleError::Loosened(status, *kind));
+            }
+            lifecycle.0.insert(status, kinds);
+        }
+        Ok(lifecycle)
+    }
+
+    /// Use this lifecycle for the rest of the program
+    pub fn install(self) -> Result<(), LifecycleError> {
+        LIFECYCLE
+            .set(self)
+            .map_err(|_| LifecycleError::AlreadyConfigured)
+    }
+
+    /// Events allowed for an item in `status`
+    pub fn allowed(&self, status: ItemStatus) -> &[EventKind] {
+        self.0.get(&status).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn allows(&self, status: ItemStatus, kind: EventKind) -> bool {
+        self.allowed(status).contains(&kind)
+    }
+}
@@ -0,0 +1,19 @@
+use diesel::prelude::*;
+
+use crate::schema::*;
+
+#[derive(Identifiable, Queryable, Selectable)]
+pub struct Member {
+    pub id: i64,
+    pub name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = members, treat_none_as_null = true)]
+pub struct InsertMember {
+    pub name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
@@ -1,4 +1,9 @@
 pub mod event;
 pub mod item;
+pub mod kit;
+pub mod kit_template;
+pub mod lifecycle;
+pub mod member;
+pub mod reservation;
 pub mod tag;
 pub mod user;
@@ -0,0 +1,155 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Associations, BoolExpressionMethods as _, ExpressionMethods as _, Identifiable, Insertable,
+    OptionalExtension as _, QueryDsl as _, Queryable, Selectable,
+};
+use diesel_async::RunQueryDsl as _;
+
+use crate::{api::ApiError, schema::*};
+
+use super::{
+    item::{Item, ItemStatus},
+    kit::Kit,
+    member::Member,
+};
+
+#[derive(Identifiable, Queryable, Selectable, Associations)]
+#[diesel(belongs_to(Member))]
+#[diesel(belongs_to(Kit))]
+pub struct Reservation {
+    pub id: i64,
+    pub member_id: i64,
+    pub kit_id: Option<i64>,
+    pub starts_ts: NaiveDateTime,
+    pub ends_ts: NaiveDateTime,
+    pub comment: Option<String>,
+}
+
+impl Reservation {
+    /// Refuse a period overlapping another reservation of one of the items,
+    /// or the loan of one of them to someone else than `member_id`.
+    /// Archived items cannot be reserved.
+    ///
+    /// Must run in the transaction inserting the reservation.
+    pub async fn check_free_items(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item_ids: &[i64],
+        member_id: i64,
+        starts_ts: NaiveDateTime,
+        ends_ts: NaiveDateTime,
+    ) -> Result<(), ApiError> {
+        // lock the items so that they cannot be reserved twice at once
+        let locked = items::table
+            .filter(items::id.eq_any(item_ids))
+            .select((items::id, items::archived_ts))
+            .for_update()
+            .load::<(i64, Option<NaiveDateTime>)>(conn)
+            .await?;
+        if let Some((item_id, _)) = locked.iter().find(|(_, archived_ts)| archived_ts.is_some()) {
+            return Err(ApiError::ItemArchived(*item_id));
+        }
+        let conflict = reservations_items::table
+            .inner_join(reservations::table)
+            .filter(reservations_items::item_id.eq_any(item_ids))
+            .filter(reservations::starts_ts.lt(ends_ts))
+            .filter(reservations::ends_ts.gt(starts_ts))
+            .select((reservations_items::item_id, reservations::id))
+            .first::<(i64, i64)>(conn)
+            .await
+            .optional()?;
+        if let Some((item_id, reservation_id)) = conflict {
+            return Err(ApiError::ReservationConflict(item_id, reservation_id));
+        }
+
+        // loans without a due date go on until the item is returned
+        let lent = items::table
+            .filter(items::id.eq_any(item_ids))
+            .filter(items::status.eq(ItemStatus::Borrowed))
+            .filter(items::borrower_id.ne(member_id))
+            .filter(items::borrowed_ts.lt(ends_ts))
+            .filter(items::due_back.is_null().or(items::due_back.gt(starts_ts)))
+            .select(items::id)
+            .first::<i64>(conn)
+            .await
+            .optional()?;
+        match lent {
+            Some(item_id) => Err(ApiError::ItemLent(item_id)),
+            None => Ok(()),
+        }
+    }
+
+    /// First reservation of an item held by someone else than `member_id`, overlapping
+    /// the period from `starts_ts` until `ends_ts`, or without end if unset, with its start
+    pub async fn held_by_other(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item_id: i64,
+        member_id: i64,
+        starts_ts: NaiveDateTime,
+        ends_ts: Option<NaiveDateTime>,
+    ) -> Result<Option<(i64, NaiveDateTime)>, ApiError> {
+        let mut query = reservations_items::table
+            .inner_join(reservations::table)
+            .filter(reservations_items::item_id.eq(item_id))
+            .filter(reservations::member_id.ne(member_id))
+            .filter(reservations::ends_ts.gt(starts_ts))
+            .select((reservations::id, reservations::starts_ts))
+            .into_boxed();
+        if let Some(ends_ts) = ends_ts {
+            query = query.filter(reservations::starts_ts.lt(ends_ts));
+        }
+        Ok(query
+            .order_by(reservations::starts_ts.asc())
+            .first::<(i64, NaiveDateTime)>(conn)
+            .await
+            .optional()?)
+    }
+
+    /// Reservation covering an item at the given time or the next one after it,
+    /// with its holder and its start
+    pub async fn next_at(
+        conn: &mut diesel_async::AsyncPgConnection,
+        item_id: i64,
+        ts: NaiveDateTime,
+    ) -> Result<Option<(i64, i64, NaiveDateTime)>, ApiError> {
+        Ok(reservations_items::table
+            .inner_join(reservations::table)
+            .filter(reservations_items::item_id.eq(item_id))
+            .filter(reservations::ends_ts.gt(ts))
+            .order_by(reservations::starts_ts.asc())
+            .select((
+                reservations::id,
+                reservations::member_id,
+                reservations::starts_ts,
+            ))
+            .first::<(i64, i64, NaiveDateTime)>(conn)
+            .await
+            .optional()?)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = reservations)]
+pub struct InsertReservation {
+    pub member_id: i64,
+    pub kit_id: Option<i64>,
+    pub starts_ts: NaiveDateTime,
+    pub ends_ts: NaiveDateTime,
+    pub comment: Option<String>,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Associations)]
+#[diesel(belongs_to(Reservation))]
+#[diesel(belongs_to(Item))]
+#[diesel(table_name = reservations_items)]
+pub struct ReservationItem {
+    id: i64,
+    pub reservation_id: i64,
+    pub item_id: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = reservations_items)]
+pub struct InsertReservationItem {
+    pub reservation_id: i64,
+    pub item_id: i64,
+}
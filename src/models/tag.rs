@@ -4,19 +4,27 @@ use crate::schema::*;
 
 use super::item::Item;
 
-#[derive(Identifiable, Queryable)]
+#[derive(Identifiable, Queryable, Selectable)]
 pub struct Tag {
     pub id: i64,
     pub name: String,
+    pub max_lifetime_years: Option<i32>,
+    pub max_service_years: Option<i32>,
+    pub inspection_after_falls: Option<i32>,
+    pub inspection_after_fall_factor: Option<f32>,
 }
 
 #[derive(Insertable)]
 #[diesel(table_name = tags)]
 pub struct InsertTag {
     pub name: String,
+    pub max_lifetime_years: Option<i32>,
+    pub max_service_years: Option<i32>,
+    pub inspection_after_falls: Option<i32>,
+    pub inspection_after_fall_factor: Option<f32>,
 }
 
-#[derive(Identifiable, Queryable, Associations)]
+#[derive(Identifiable, Queryable, Selectable, Associations)]
 #[diesel(belongs_to(Tag))]
 #[diesel(belongs_to(Item))]
 #[diesel(table_name = items_tags)]
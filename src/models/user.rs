@@ -13,6 +13,7 @@ pub struct User {
     pub perm_items: bool,
     pub perm_action_inspect: bool,
     pub perm_action_lend: bool,
+    pub perm_action_maintain: bool,
 }
 
 #[derive(Insertable)]
@@ -26,6 +27,7 @@ pub struct InsertUser {
     pub perm_items: bool,
     pub perm_action_inspect: bool,
     pub perm_action_lend: bool,
+    pub perm_action_maintain: bool,
 }
 
 #[derive(AsChangeset)]
@@ -38,6 +40,7 @@ struct UpdateUserAdmin {
     perm_items: Option<bool>,
     perm_action_inspect: Option<bool>,
     perm_action_lend: Option<bool>,
+    perm_action_maintain: Option<bool>,
 }
 
 #[derive(AsChangeset)]
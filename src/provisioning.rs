@@ -11,13 +11,13 @@ use crate::{
     schema::*,
 };
 
-// id, name
-const TAGS: &[(i64, &'static str)] = &[
-    (0, "Corde simple"),
-    (1, "Corde double"),
-    (2, "Système d'assurage"),
-    (3, "Sangle"),
-    (4, "Friend"),
+// id, name, max lifetime years
+const TAGS: &[(i64, &'static str, Option<i32>)] = &[
+    (0, "Corde simple", Some(10)),
+    (1, "Corde double", Some(10)),
+    (2, "Système d'assurage", None),
+    (3, "Sangle", Some(10)),
+    (4, "Friend", None),
 ];
 
 // id, name, inspection period days, tags
@@ -179,6 +179,10 @@ pub(crate) async fn provision(
             TAGS.iter()
                 .map(|tag| InsertTag {
                     name: tag.1.to_owned(),
+                    max_lifetime_years: tag.2,
+                    max_service_years: None,
+                    inspection_after_falls: None,
+                    inspection_after_fall_factor: None,
                 })
                 .collect::<Vec<_>>(),
         )
@@ -191,6 +195,10 @@ pub(crate) async fn provision(
             name: item.1.to_owned(),
             serial_number: item.2.map(|s| s.to_owned()),
             inspection_period_days: item.3.map(PgInterval::from_days),
+            max_lifetime_years: None,
+            max_service_years: None,
+            inspection_after_falls: None,
+            inspection_after_fall_factor: None,
         }
         .insert_into(items::table)
         .returning(items::id)
@@ -15,6 +15,23 @@ diesel::table! {
         name -> Varchar,
         inspection_period_days -> Nullable<Interval>,
         serial_number -> Nullable<Varchar>,
+        status -> Varchar,
+        last_event_ts -> Nullable<Timestamp>,
+        last_inspection_ts -> Nullable<Timestamp>,
+        max_lifetime_years -> Nullable<Int4>,
+        max_service_years -> Nullable<Int4>,
+        manufactured_ts -> Nullable<Timestamp>,
+        in_service_ts -> Nullable<Timestamp>,
+        inspection_after_falls -> Nullable<Int4>,
+        inspection_after_fall_factor -> Nullable<Float4>,
+        falls_since_inspection -> Int4,
+        worst_fall_factor_since_inspection -> Nullable<Float4>,
+        last_used_ts -> Nullable<Timestamp>,
+        borrower -> Nullable<Text>,
+        borrowed_ts -> Nullable<Timestamp>,
+        due_back -> Nullable<Timestamp>,
+        borrower_id -> Nullable<Int8>,
+        archived_ts -> Nullable<Timestamp>,
     }
 }
 
@@ -26,10 +43,74 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    kit_templates (id) {
+        id -> Int8,
+        name -> Varchar,
+    }
+}
+
+diesel::table! {
+    kit_templates_tags (id) {
+        id -> Int8,
+        kit_template_id -> Int8,
+        tag_id -> Int8,
+        quantity -> Int4,
+    }
+}
+
+diesel::table! {
+    kits (id) {
+        id -> Int8,
+        name -> Varchar,
+        closed_ts -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    kits_items (id) {
+        id -> Int8,
+        kit_id -> Int8,
+        item_id -> Int8,
+    }
+}
+
+diesel::table! {
+    members (id) {
+        id -> Int8,
+        name -> Varchar,
+        email -> Nullable<Varchar>,
+        phone -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    reservations (id) {
+        id -> Int8,
+        member_id -> Int8,
+        kit_id -> Nullable<Int8>,
+        starts_ts -> Timestamp,
+        ends_ts -> Timestamp,
+        comment -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    reservations_items (id) {
+        id -> Int8,
+        reservation_id -> Int8,
+        item_id -> Int8,
+    }
+}
+
 diesel::table! {
     tags (id) {
         id -> Int8,
         name -> Varchar,
+        max_lifetime_years -> Nullable<Int4>,
+        max_service_years -> Nullable<Int4>,
+        inspection_after_falls -> Nullable<Int4>,
+        inspection_after_fall_factor -> Nullable<Float4>,
     }
 }
 
@@ -44,11 +125,34 @@ diesel::table! {
         perm_items -> Bool,
         perm_action_inspect -> Bool,
         perm_action_lend -> Bool,
+        perm_action_maintain -> Bool,
     }
 }
 
 diesel::joinable!(events -> items (item_id));
+diesel::joinable!(items -> members (borrower_id));
 diesel::joinable!(items_tags -> items (item_id));
 diesel::joinable!(items_tags -> tags (tag_id));
+diesel::joinable!(kit_templates_tags -> kit_templates (kit_template_id));
+diesel::joinable!(kit_templates_tags -> tags (tag_id));
+diesel::joinable!(kits_items -> items (item_id));
+diesel::joinable!(kits_items -> kits (kit_id));
+diesel::joinable!(reservations -> kits (kit_id));
+diesel::joinable!(reservations -> members (member_id));
+diesel::joinable!(reservations_items -> items (item_id));
+diesel::joinable!(reservations_items -> reservations (reservation_id));
 
-diesel::allow_tables_to_appear_in_same_query!(events, items, items_tags, tags, users,);
+diesel::allow_tables_to_appear_in_same_query!(
+    events,
+    items,
+    items_tags,
+    kit_templates,
+    kit_templates_tags,
+    kits,
+    kits_items,
+    members,
+    reservations,
+    reservations_items,
+    tags,
+    users,
+);
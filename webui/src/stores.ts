@@ -1,6 +1,8 @@
 import { defineStore } from 'pinia'
 import type { Item } from './bindings/Item'
+import type { ItemPage } from './bindings/ItemPage'
 import type { CreateItem } from './bindings/CreateItem'
+import type { CreatedItem } from './bindings/CreatedItem'
 import type { Tag } from './bindings/Tag'
 import type { ItemDetails } from './bindings/ItemDetails'
 import type { DataTableFilterMeta } from 'primevue/datatable'
@@ -57,13 +59,21 @@ export const useItemsStore = defineStore('items', {
     },
 
     async refresh() {
-      this.items = await Requester.get('/items')
+      // follow the pages until the last one
+      let items: Item[] = []
+      let cursor: bigint | null = null
+      do {
+        const page: ItemPage = await Requester.get('/items' + (cursor !== null ? '?cursor=' + cursor : ''))
+        items = items.concat(page.items)
+        cursor = page.next_cursor
+      } while (cursor !== null)
+      this.items = items
     },
 
-    async create(create_item: CreateItem): Promise<Item> {
-      const item: Item = await Requester.post('/items', create_item);
+    async create(create_item: CreateItem): Promise<CreatedItem> {
+      const created: CreatedItem = await Requester.post('/items', create_item);
       this.refresh()
-      return item
+      return created
     }
   }
 })